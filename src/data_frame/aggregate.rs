@@ -5,14 +5,26 @@ use crate::data_frame::core::FrameData;
 use crate::data_frame::strided_index_view::StridedIndexView;
 use crate::data_frame::util::mean::Mean;
 use crate::data_frame::util::tri_product_index_view::TriProductIndexView;
-use crate::mapped_index::VariableRange;
-use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist};
+use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist, ReducedIndex};
+use crate::mapped_index::numeric_range::NumericRangeIndex;
 use crate::mapped_index::util::as_refs::{AsRefs, HRefs};
 use crate::mapped_index::util::concat::{HLConcat, HListConcat};
 use crate::mapped_index::util::pluck_split::{
-    PluckAt, PluckLeft, PluckRemainder, PluckRight, PluckSplit, PluckSplitImpl,
+    PluckAt, PluckLeft, PluckRemainder, PluckReplace, PluckRight, PluckSplit, PluckSplitImpl,
 };
+use crate::mapped_index::{FromSize, VariableRange};
+use frunk::HCons;
+use frunk::hlist::h_cons;
 use itertools::Itertools;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A frame holding one `T` per position of the dimensions left after reducing away `At`.
+pub type ReducedFrame<At, Indices, T> = DataFrame<ReducedIndex<At, Indices>, Vec<T>>;
+
+/// The index of `Indices` with dimension `At` replaced by a range of bin numbers.
+pub type BinnedIndex<At, Indices> =
+    CompoundIndex<PluckReplace<At, Indices, NumericRangeIndex<usize>>>;
 
 pub struct IterOverDim<'a, Data, Plucked, Left, Right, Remainder>
 where
@@ -155,23 +167,66 @@ where
         let l_size = l.size();
         let m_size = m.size();
         let r_size = r.size();
-        let agg_data = (0..l_size)
-            .flat_map(|l_i| {
-                let f = &f;
-                let data = self.data();
-                (0..r_size).map(move |r_i| {
-                    f(StridedIndexView::new(
-                        l_i * m_size * r_size + r_i,
-                        r_size,
-                        m_size,
-                        data,
-                    ))
-                })
-            })
+        let whole_dim = std::iter::once((0, m_size));
+        let agg_data = strided_views(self.data(), (l_size, m_size, r_size), whole_dim)
+            .map(&f)
             .collect_vec();
         DataFrame::new(CompoundIndex::new(l.concat(r)), agg_data)
     }
 
+    /// Aggregate consecutive groups of `bin` values along the dimension specified by typenum.
+    ///
+    /// The dimension is replaced by a `NumericRangeIndex<usize>` of bin numbers with
+    /// `ceil(size / bin)` entries; the last bin may be partial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use slice_and_dice::data_frame::core::DataFrame;
+    /// use slice_and_dice::mapped_index::numeric_range::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 5),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5]);
+    /// let binned = df.aggregate_dim_binned::<Dim1, _, i32>(2, |view| view.copied().sum());
+    /// assert_eq!(binned.data(), &vec![3, 7, 5]);
+    /// ```
+    pub fn aggregate_dim_binned<Idx, F, R>(
+        &self,
+        bin: usize,
+        f: F,
+    ) -> DataFrame<BinnedIndex<Idx, Indices>, Vec<R>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>:
+            IndexHlist + HListConcat<HCons<NumericRangeIndex<usize>, PluckRight<Idx, Indices>>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckReplace<Idx, Indices, NumericRangeIndex<usize>>: IndexHlist,
+        F: for<'any> Fn(StridedIndexView<'any, D>) -> R,
+    {
+        assert!(bin > 0, "bin size must be greater than zero");
+        let refs = self.index().indices.clone();
+        let (l, m, r) = refs.pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+        let n_bins = m_size.div_ceil(bin);
+        let bin_starts = (0..n_bins).map(|b_i| (b_i * bin, bin.min(m_size - b_i * bin)));
+        let agg_data = strided_views(self.data(), (l_size, m_size, r_size), bin_starts)
+            .map(&f)
+            .collect_vec();
+        let bins = NumericRangeIndex::from_size(n_bins);
+        DataFrame::new(CompoundIndex::new(l.concat(h_cons(bins, r))), agg_data)
+    }
+
     /// Compute the mean over the dimension specified by typenum.
     ///
     /// Uses the Mean trait to compute the mean of each strided slice.
@@ -201,6 +256,163 @@ where
                 .expect("mean_over_dim: cannot compute mean of zero elements")
         })
     }
+
+    /// Compute the `(min, max)` of every slice over the dimension specified by typenum, in a
+    /// single pass over each slice.
+    ///
+    /// # Panics
+    /// Panics if the dimension is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use slice_and_dice::data_frame::core::DataFrame;
+    /// use slice_and_dice::mapped_index::numeric_range::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![2, 9, 4]);
+    /// assert_eq!(df.min_max_over_dim::<Dim1>().data(), &vec![(2, 9)]);
+    /// ```
+    pub fn min_max_over_dim<Idx>(&self) -> ReducedFrame<Idx, Indices, (D::Output, D::Output)>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist,
+        D::Output: Copy + PartialOrd,
+    {
+        self.aggregate_over_dim::<Idx, _, (D::Output, D::Output)>(|mut iter| {
+            let first = *iter
+                .next()
+                .expect("min_max_over_dim: cannot compute extremes of zero elements");
+            iter.fold((first, first), |(min, max), &v| {
+                (if v < min { v } else { min }, if v > max { v } else { max })
+            })
+        })
+    }
+
+    /// Compute the most frequent value of every slice over the dimension specified by
+    /// typenum. Ties go to the value that occurs first in the slice.
+    ///
+    /// # Panics
+    /// Panics if the dimension is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use slice_and_dice::data_frame::core::DataFrame;
+    /// use slice_and_dice::mapped_index::numeric_range::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 4),
+    /// ]);
+    /// let df = DataFrame::new(index, vec!['b', 'a', 'a', 'b']);
+    /// assert_eq!(df.mode_over_dim::<Dim1>().data(), &vec!['b']);
+    /// ```
+    pub fn mode_over_dim<Idx>(&self) -> ReducedFrame<Idx, Indices, D::Output>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist,
+        D::Output: Eq + Hash + Clone,
+    {
+        self.aggregate_over_dim::<Idx, _, D::Output>(|iter| {
+            let mut counts: HashMap<&D::Output, usize> = HashMap::new();
+            let mut order = Vec::new();
+            for v in iter {
+                *counts.entry(v).or_insert_with(|| {
+                    order.push(v);
+                    0
+                }) += 1;
+            }
+            // Only a strictly greater count replaces the best, so ties keep the first value.
+            let mut best: Option<(&D::Output, usize)> = None;
+            for v in order {
+                let count = counts[v];
+                if best.is_none_or(|(_, best_count)| count > best_count) {
+                    best = Some((v, count));
+                }
+            }
+            best.expect("mode_over_dim: cannot compute mode of zero elements")
+                .0
+                .clone()
+        })
+    }
+
+    /// Compute the position of the maximum of every slice over the dimension specified by
+    /// typenum, counted along that dimension. Ties go to the first maximum.
+    ///
+    /// # Panics
+    /// Panics if the dimension is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use slice_and_dice::data_frame::core::DataFrame;
+    /// use slice_and_dice::mapped_index::numeric_range::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![2, 9, 4]);
+    /// assert_eq!(df.argmax_over_dim::<Dim1>().data(), &vec![1]);
+    /// ```
+    pub fn argmax_over_dim<Idx>(&self) -> ReducedFrame<Idx, Indices, usize>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist,
+        D::Output: PartialOrd,
+    {
+        self.aggregate_over_dim::<Idx, _, usize>(|mut iter| {
+            let first = iter
+                .next()
+                .expect("argmax_over_dim: cannot compute the maximum of zero elements");
+            // Only a strictly greater value replaces the best, so ties keep the first position.
+            let (best, _) =
+                iter.enumerate().fold(
+                    (0, first),
+                    |(best, max), (i, v)| {
+                        if v > max { (i + 1, v) } else { (best, max) }
+                    },
+                );
+            best
+        })
+    }
+}
+
+/// Views of runs along the middle dimension of a flat row-major layout of shape
+/// `(l_size, m_size, r_size)`.
+///
+/// For every `(start, len)` in `runs`, yields a view of the `len` values from position `start`
+/// along the middle dimension, for each position in the outer and inner dimensions. The views
+/// come out in row-major order, so collecting a result per view gives the data of a frame in
+/// which the middle dimension has one position per run.
+pub(crate) fn strided_views<'a, D: FrameData>(
+    data: &'a D,
+    (l_size, m_size, r_size): (usize, usize, usize),
+    runs: impl Iterator<Item = (usize, usize)> + Clone + 'a,
+) -> impl Iterator<Item = StridedIndexView<'a, D>> + 'a {
+    (0..l_size).flat_map(move |l_i| {
+        runs.clone().flat_map(move |(start, len)| {
+            (0..r_size).map(move |r_i| {
+                StridedIndexView::new((l_i * m_size + start) * r_size + r_i, r_size, len, data)
+            })
+        })
+    })
 }
 
 #[cfg(test)]
@@ -251,6 +463,68 @@ mod tests {
         assert!((mean_cols.data[1] - 50.0f64).abs() < 1e-10f64);
     }
 
+    #[test]
+    fn test_min_max_over_dim() {
+        // 2x3 frame:
+        // [3, 1, 8]
+        // [5, 7, 2]
+        let compound_index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(compound_index, vec![3, 1, 8, 5, 7, 2]);
+
+        let per_column = df.min_max_over_dim::<Dim0>();
+        assert_eq!(per_column.data(), &vec![(3, 5), (1, 7), (2, 8)]);
+
+        let per_row = df.min_max_over_dim::<Dim1>();
+        assert_eq!(per_row.data(), &vec![(1, 8), (2, 7)]);
+    }
+
+    #[test]
+    fn test_mode_over_dim() {
+        // 3x2 frame:
+        // ["x", "p"]
+        // ["y", "q"]
+        // ["y", "p"]
+        let compound_index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 3),
+            NumericRangeIndex::<i32>::new(0, 2)
+        ]);
+        let df = DataFrame::new(compound_index, vec!["x", "p", "y", "q", "y", "p"]);
+
+        let per_column = df.mode_over_dim::<Dim0>();
+        assert_eq!(per_column.data(), &vec!["y", "p"]);
+
+        // Every row holds two distinct labels, so the first one wins the tie
+        let per_row = df.mode_over_dim::<Dim1>();
+        assert_eq!(per_row.data(), &vec!["x", "y", "y"]);
+    }
+
+    #[test]
+    fn test_argmax_over_dim() {
+        // 3x2 frame:
+        // [1, 8]
+        // [5, 2]
+        // [5, 9]
+        let compound_index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 3),
+            NumericRangeIndex::<i32>::new(10, 12)
+        ]);
+        let df = DataFrame::new(compound_index, vec![1.0, 8.0, 5.0, 2.0, 5.0, 9.0]);
+
+        // The first column has a tie between rows 1 and 2; the first one wins
+        let per_column = df.argmax_over_dim::<Dim0>();
+        assert_eq!(
+            per_column.index().indices,
+            hlist![NumericRangeIndex::new(10, 12)]
+        );
+        assert_eq!(per_column.data(), &vec![1, 2]);
+
+        let per_row = df.argmax_over_dim::<Dim1>();
+        assert_eq!(per_row.data(), &vec![1, 0, 1]);
+    }
+
     // Test aggregate_over_dim with a custom aggregation function
     #[test]
     fn test_aggregate_over_dim() {
@@ -366,6 +640,50 @@ mod tests {
         assert_eq!(product_cols.data[0], 100 * 101 * 102);
     }
 
+    // Test mean-downsampling a dimension into bins of two
+    #[test]
+    fn test_aggregate_dim_binned() {
+        // Create a 2D DataFrame with dimensions 2x6
+        let index1 = NumericRangeIndex::<i32>::new(0, 2); // [0, 1]
+        let index2 = NumericRangeIndex::<i32>::new(0, 6); // [0, 1, 2, 3, 4, 5]
+        let df = DataFrame::new(
+            CompoundIndex::new(hlist![index1, index2]),
+            vec![
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, // first row
+                10.0, 20.0, 30.0, 40.0, 50.0, 60.0, // second row
+            ],
+        );
+
+        let binned = df.aggregate_dim_binned::<Dim1, _, f64>(2, |view| {
+            f64::mean_from_iter(view).expect("bins are never empty")
+        });
+
+        // The binned dimension has ceil(6 / 2) = 3 entries
+        assert_eq!(binned.index().indices.head.size(), 2);
+        assert_eq!(binned.index().indices.tail.head.size(), 3);
+        assert_eq!(binned.data(), &vec![1.5, 3.5, 5.5, 15.0, 35.0, 55.0]);
+
+        // Binning along the first dimension keeps the trailing dimension intact
+        let binned_rows = df.aggregate_dim_binned::<Dim0, _, f64>(2, |view| {
+            f64::mean_from_iter(view).expect("bins are never empty")
+        });
+        assert_eq!(binned_rows.data(), &vec![5.5, 11.0, 16.5, 22.0, 27.5, 33.0]);
+    }
+
+    // Test that a partial last bin aggregates only its available values
+    #[test]
+    fn test_aggregate_dim_binned_partial() {
+        let index1 = NumericRangeIndex::<i32>::new(0, 1); // [0]
+        let index2 = NumericRangeIndex::<i32>::new(0, 5); // [0, 1, 2, 3, 4]
+        let df = DataFrame::new(
+            CompoundIndex::new(hlist![index1, index2]),
+            vec![1, 2, 3, 4, 5],
+        );
+
+        let binned = df.aggregate_dim_binned::<Dim1, _, usize>(2, |view| view.len());
+        assert_eq!(binned.data(), &vec![2, 2, 1]);
+    }
+
     // Test iter_over_dim functionality
     #[test]
     fn test_iter_over_dim() {
@@ -0,0 +1,786 @@
+//! Transformations that operate on whole slices along one dimension of a compound DataFrame,
+//! keeping the other dimensions intact.
+use super::core::{DataFrame, FrameData};
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist, ReducedIndex};
+use crate::mapped_index::util::concat::HListConcat;
+use crate::mapped_index::util::pluck_split::{
+    PluckAt, PluckLeft, PluckRemainder, PluckReplace, PluckRight, PluckSplitImpl,
+};
+use crate::mapped_index::{IntoValues, SubRange, VariableRange};
+use frunk::HCons;
+use frunk::hlist::{Selector, h_cons};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// The labels of dimension `At` of `Indices`, as picked out by `select_dim`.
+pub type SelectedLabels<At, Indices> =
+    CategoricalRange<<PluckAt<At, Indices> as IntoValues>::OwnedValue>;
+
+/// The indices of `Indices` with dimension `At` replaced by [`SelectedLabels`].
+pub type SelectedIndices<At, Indices> = PluckReplace<At, Indices, SelectedLabels<At, Indices>>;
+
+impl<Indices, D> DataFrame<CompoundIndex<Indices>, D>
+where
+    Indices: IndexHlist,
+    D: FrameData,
+{
+    /// Transform every slice along the dimension specified by typenum, materializing each
+    /// slice as an owned `Vec` and scattering the returned values back into place.
+    ///
+    /// Unlike the zero-copy views handed out by `aggregate_over_dim`, this lets `f` sort or
+    /// randomly access the slice.
+    ///
+    /// # Panics
+    /// Panics if `f` returns a `Vec` whose length differs from the size of the dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+    /// // Reverse each column
+    /// let flipped = df.apply_along_axis::<Dim0, _, _>(|mut col| {
+    ///     col.reverse();
+    ///     col
+    /// });
+    /// assert_eq!(flipped.data(), &vec![3, 4, 1, 2]);
+    /// ```
+    pub fn apply_along_axis<Idx, F, R>(self, mut f: F) -> DataFrame<CompoundIndex<Indices>, Vec<R>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: VariableRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D::Output: Clone,
+        F: FnMut(Vec<D::Output>) -> Vec<R>,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+
+        let mut lanes = Vec::with_capacity(l_size * r_size);
+        for l_i in 0..l_size {
+            for r_i in 0..r_size {
+                let base = l_i * m_size * r_size + r_i;
+                let lane = (0..m_size)
+                    .map(|m_i| self.data[base + m_i * r_size].clone())
+                    .collect();
+                lanes.push(f(lane));
+            }
+        }
+
+        let data = scatter_lanes(lanes, l_size, m_size, r_size, self.data.len());
+        DataFrame::new(self.index, data)
+    }
+
+    /// Apply `f` to every pair of adjacent values `(x[k - 1], x[k])` along the dimension
+    /// specified by typenum, shrinking that dimension by one.
+    ///
+    /// Each result is labelled with the position `k` of the later element, so the dimension
+    /// loses its first value.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 5, 2]);
+    /// let rising = df.apply_pairs_over_dim::<Dim1, _, _>(|a, b| b > a);
+    /// assert_eq!(rising.data(), &vec![true, false]);
+    /// ```
+    pub fn apply_pairs_over_dim<Idx, R, F>(&self, f: F) -> DataFrame<CompoundIndex<Indices>, Vec<R>>
+    where
+        Indices: PluckSplitImpl<Idx> + Selector<PluckAt<Idx, Indices>, Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: SubRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        F: Fn(&D::Output, &D::Output) -> R,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+
+        let data = (0..l_size)
+            .flat_map(|l_i| (1..m_size).map(move |m_i| (l_i * m_size + m_i) * r_size))
+            .flat_map(|base| base..base + r_size)
+            .map(|i| f(&self.data[i - r_size], &self.data[i]))
+            .collect();
+
+        let mut indices = self.index.indices.clone();
+        let dim: &mut PluckAt<Idx, Indices> = indices.get_mut();
+        *dim = dim.sub_range(m_size.min(1), m_size);
+        DataFrame::new(CompoundIndex::new(indices), data)
+    }
+
+    /// Restrict the dimension specified by typenum to the positions `start..end`, keeping
+    /// every other dimension in full, like numpy's `a[:, start:end]`.
+    ///
+    /// # Panics
+    /// Panics if `start > end` or `end` exceeds the size of the dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+    /// let tail = df.slice_dim::<Dim0>(1, 3);
+    /// assert_eq!(tail.index().indices.head, NumericRangeIndex::new(1, 3));
+    /// assert_eq!(tail.data(), &vec![3, 4, 5, 6]);
+    /// ```
+    pub fn slice_dim<Idx>(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx> + Selector<PluckAt<Idx, Indices>, Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: SubRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D::Output: Clone,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+        assert!(
+            start <= end && end <= m_size,
+            "Slice {}..{} out of bounds for a dimension of size {}",
+            start,
+            end,
+            m_size
+        );
+
+        // The kept positions of each outer slice form one contiguous run of the flat data.
+        let data = (0..l_size)
+            .flat_map(|l_i| (l_i * m_size + start) * r_size..(l_i * m_size + end) * r_size)
+            .map(|i| self.data[i].clone())
+            .collect();
+
+        let mut indices = self.index.indices.clone();
+        let dim: &mut PluckAt<Idx, Indices> = indices.get_mut();
+        *dim = dim.sub_range(start, end);
+        DataFrame::new(CompoundIndex::new(indices), data)
+    }
+
+    /// Gather the given positions along the dimension specified by typenum, in the given
+    /// order and possibly repeated, keeping every other dimension in full, like numpy's
+    /// `a[:, positions]`.
+    ///
+    /// The dimension is replaced by a [`CategoricalRange`] of the selected labels.
+    ///
+    /// # Panics
+    /// Panics if any position is out of bounds for the dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(10, 13),
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3]);
+    /// let picked = df.select_dim::<Dim0>(&[2, 2, 0]);
+    /// assert_eq!(picked.index().indices.head.values, vec![12, 12, 10]);
+    /// assert_eq!(picked.data(), &vec![3, 3, 1]);
+    /// ```
+    pub fn select_dim<Idx>(
+        &self,
+        positions: &[usize],
+    ) -> DataFrame<CompoundIndex<SelectedIndices<Idx, Indices>>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>:
+            IndexHlist + HListConcat<HCons<SelectedLabels<Idx, Indices>, PluckRight<Idx, Indices>>>,
+        PluckAt<Idx, Indices>: IntoValues,
+        <PluckAt<Idx, Indices> as IntoValues>::OwnedValue: Sync + Clone,
+        PluckRight<Idx, Indices>: IndexHlist,
+        SelectedIndices<Idx, Indices>: IndexHlist,
+        D::Output: Clone,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+        if let Some(&bad) = positions.iter().find(|&&p| p >= m_size) {
+            panic!(
+                "Position {} out of bounds for a dimension of size {}",
+                bad, m_size
+            );
+        }
+
+        let data = (0..l_size)
+            .flat_map(|l_i| {
+                positions.iter().flat_map(move |&p| {
+                    let base = (l_i * m_size + p) * r_size;
+                    base..base + r_size
+                })
+            })
+            .map(|i| self.data[i].clone())
+            .collect();
+
+        let labels: Vec<_> = m.into_values().collect();
+        let selected =
+            CategoricalRange::new(positions.iter().map(|&p| labels[p].clone()).collect());
+        DataFrame::new(CompoundIndex::new(l.concat(h_cons(selected, r))), data)
+    }
+
+    /// Combine every value with the value of `other` at the same position in the remaining
+    /// dimensions, broadcasting `other` across the dimension specified by typenum.
+    ///
+    /// `other` is indexed like the result of reducing that dimension, e.g. by
+    /// `mean_over_dim`, which makes this the usual way to normalize slices.
+    ///
+    /// # Panics
+    /// Panics if the index of `other` differs from the remaining dimensions of `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1.0, 3.0, 2.0, 6.0]);
+    /// let row_sums = df.aggregate_over_dim::<Dim1, _, f64>(|row| row.sum());
+    /// let shares = df.broadcast_binary_over_dim::<Dim1, _, _, _>(&row_sums, |v, total| v / total);
+    /// assert_eq!(shares.data(), &vec![0.25, 0.75, 0.25, 0.75]);
+    /// ```
+    pub fn broadcast_binary_over_dim<Idx, U, V, F>(
+        &self,
+        other: &DataFrame<ReducedIndex<Idx, Indices>, Vec<U>>,
+        f: F,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<V>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist + PartialEq,
+        F: Fn(&D::Output, &U) -> V,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let m_size = m.size();
+        let r_size = r.size();
+        assert!(
+            other.index.indices == l.concat(r),
+            "Broadcast frame index must match the remaining dimensions"
+        );
+
+        let data = (0..self.data.len())
+            .map(|i| {
+                let (l_i, r_i) = (i / (m_size * r_size), i % r_size);
+                f(&self.data[i], &other.data[l_i * r_size + r_i])
+            })
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+
+    /// Subtract `other` from every value, broadcast across the dimension specified by
+    /// typenum; see [`DataFrame::broadcast_binary_over_dim`].
+    pub fn sub_along_dim<Idx>(
+        &self,
+        other: &DataFrame<ReducedIndex<Idx, Indices>, Vec<D::Output>>,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist + PartialEq,
+        D::Output: Copy + Sub<Output = D::Output>,
+    {
+        self.broadcast_binary_over_dim::<Idx, _, _, _>(other, |&a, &b| a - b)
+    }
+
+    /// Add `other` to every value, broadcast across the dimension specified by typenum;
+    /// see [`DataFrame::broadcast_binary_over_dim`].
+    pub fn add_along_dim<Idx>(
+        &self,
+        other: &DataFrame<ReducedIndex<Idx, Indices>, Vec<D::Output>>,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist + PartialEq,
+        D::Output: Copy + Add<Output = D::Output>,
+    {
+        self.broadcast_binary_over_dim::<Idx, _, _, _>(other, |&a, &b| a + b)
+    }
+
+    /// Multiply every value by `other`, broadcast across the dimension specified by
+    /// typenum; see [`DataFrame::broadcast_binary_over_dim`].
+    pub fn mul_along_dim<Idx>(
+        &self,
+        other: &DataFrame<ReducedIndex<Idx, Indices>, Vec<D::Output>>,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist + PartialEq,
+        D::Output: Copy + Mul<Output = D::Output>,
+    {
+        self.broadcast_binary_over_dim::<Idx, _, _, _>(other, |&a, &b| a * b)
+    }
+
+    /// Divide every value by `other`, broadcast across the dimension specified by typenum;
+    /// see [`DataFrame::broadcast_binary_over_dim`].
+    pub fn div_along_dim<Idx>(
+        &self,
+        other: &DataFrame<ReducedIndex<Idx, Indices>, Vec<D::Output>>,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        PluckLeft<Idx, Indices>: IndexHlist + HListConcat<PluckRight<Idx, Indices>>,
+        PluckAt<Idx, Indices>: VariableRange,
+        PluckRight<Idx, Indices>: IndexHlist,
+        PluckRemainder<Idx, Indices>: IndexHlist + PartialEq,
+        D::Output: Copy + Div<Output = D::Output>,
+    {
+        self.broadcast_binary_over_dim::<Idx, _, _, _>(other, |&a, &b| a / b)
+    }
+
+    /// Consecutive differences `x[k] - x[k - 1]` along the dimension specified by typenum,
+    /// labelled like [`DataFrame::apply_pairs_over_dim`].
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 4, 9]);
+    /// let diffs = df.diff_over_dim::<Dim0>();
+    /// assert_eq!(diffs.index().indices.head, NumericRangeIndex::new(1, 3));
+    /// assert_eq!(diffs.data(), &vec![3, 5]);
+    /// ```
+    pub fn diff_over_dim<Idx>(
+        &self,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<<D::Output as Sub>::Output>>
+    where
+        Indices: PluckSplitImpl<Idx> + Selector<PluckAt<Idx, Indices>, Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: SubRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D::Output: Copy + Sub,
+    {
+        self.apply_pairs_over_dim::<Idx, _, _>(|&prev, &next| next - prev)
+    }
+
+    /// Running sums along the dimension specified by typenum, keeping the shape of the
+    /// frame, like numpy's `cumsum(axis=...)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 4, 9]);
+    /// assert_eq!(df.cumsum_over_dim::<Dim0>().data(), &vec![1, 5, 14]);
+    /// ```
+    pub fn cumsum_over_dim<Idx>(&self) -> DataFrame<CompoundIndex<Indices>, Vec<D::Output>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: VariableRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D::Output: Copy + Add<Output = D::Output>,
+    {
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+
+        let mut data: Vec<D::Output> = self.data.iter().copied().collect();
+        // Each position adds onto the running sum one step back along the dimension, which
+        // precedes it in the flat layout and so is already complete.
+        for l_i in 0..l_size {
+            for m_i in 1..m_size {
+                let base = (l_i * m_size + m_i) * r_size;
+                for i in base..base + r_size {
+                    data[i] = data[i - r_size] + data[i];
+                }
+            }
+        }
+        DataFrame::new(self.index.clone(), data)
+    }
+
+    /// Parallel version of [`DataFrame::apply_along_axis`], transforming the slices on the
+    /// rayon thread pool.
+    ///
+    /// # Panics
+    /// Panics if `f` returns a `Vec` whose length differs from the size of the dimension.
+    #[cfg(feature = "rayon")]
+    pub fn apply_along_axis_par<Idx, F, R>(self, f: F) -> DataFrame<CompoundIndex<Indices>, Vec<R>>
+    where
+        Indices: PluckSplitImpl<Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: VariableRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D: Sync,
+        D::Output: Clone + Send,
+        F: Fn(Vec<D::Output>) -> Vec<R> + Sync,
+        R: Send,
+    {
+        use rayon::prelude::*;
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+
+        let lanes: Vec<Vec<R>> = (0..l_size * r_size)
+            .into_par_iter()
+            .map(|lane_i| {
+                let base = (lane_i / r_size) * m_size * r_size + lane_i % r_size;
+                let lane = (0..m_size)
+                    .map(|m_i| self.data[base + m_i * r_size].clone())
+                    .collect();
+                f(lane)
+            })
+            .collect();
+
+        let data = scatter_lanes(lanes, l_size, m_size, r_size, self.data.len());
+        DataFrame::new(self.index, data)
+    }
+}
+
+/// Interleave transformed lanes back into the flat row-major layout, where lane
+/// `l_i * r_size + r_i` holds the values along the middle dimension at `(l_i, _, r_i)`.
+fn scatter_lanes<R>(
+    lanes: Vec<Vec<R>>,
+    l_size: usize,
+    m_size: usize,
+    r_size: usize,
+    len: usize,
+) -> Vec<R> {
+    let mut lanes: Vec<_> = lanes
+        .into_iter()
+        .map(|lane| {
+            assert_eq!(
+                lane.len(),
+                m_size,
+                "apply_along_axis closure must preserve the slice length"
+            );
+            lane.into_iter()
+        })
+        .collect();
+
+    // Walk the flat layout, taking the next value from the lane each position belongs to.
+    let mut data = Vec::with_capacity(len);
+    for l_i in 0..l_size {
+        for _ in 0..m_size {
+            for lane in &mut lanes[l_i * r_size..(l_i + 1) * r_size] {
+                data.extend(lane.next());
+            }
+        }
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::compound_index::{Dim0, Dim1, Dim2};
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+    use frunk::hlist;
+
+    #[test]
+    fn test_apply_along_axis_sorts_rows() {
+        // 2x3 frame:
+        // [3, 1, 2]
+        // [6, 5, 4]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index.clone(), vec![3, 1, 2, 6, 5, 4]);
+
+        let sorted = df.apply_along_axis::<Dim1, _, _>(|mut row| {
+            row.sort();
+            row
+        });
+
+        assert_eq!(sorted.index(), &index);
+        assert_eq!(sorted.data(), &vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_apply_along_axis_middle_dim() {
+        // 2x2x2 frame; cumulative sums along the middle dimension
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 2)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let result = df.clone().apply_along_axis::<Dim1, _, _>(|lane| {
+            lane.iter()
+                .scan(0, |acc, v| {
+                    *acc += v;
+                    Some(*acc)
+                })
+                .collect()
+        });
+        assert_eq!(result.data(), &vec![1, 2, 4, 6, 5, 6, 12, 14]);
+
+        let result = df
+            .clone()
+            .apply_along_axis::<Dim0, _, _>(|lane| lane.iter().map(|v| v * lane[0]).collect());
+        assert_eq!(result.data(), &vec![1, 4, 9, 16, 5, 12, 21, 32]);
+
+        let result = df.apply_along_axis::<Dim2, _, _>(|lane| vec![lane[1], lane[0]]);
+        assert_eq!(result.data(), &vec![2, 1, 4, 3, 6, 5, 8, 7]);
+    }
+
+    #[test]
+    fn test_diff_over_dim() {
+        // 2x4 frame:
+        // [1, 2, 4,  8]
+        // [0, 3, 3, 10]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(10, 14)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 4, 8, 0, 3, 3, 10]);
+
+        let diffs = df.diff_over_dim::<Dim1>();
+
+        assert_eq!(
+            diffs.index().indices,
+            hlist![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(11, 14)]
+        );
+        assert_eq!(diffs.data(), &vec![1, 2, 4, 3, 0, 7]);
+
+        let diffs = df.diff_over_dim::<Dim0>();
+        assert_eq!(diffs.index().indices.head, NumericRangeIndex::new(1, 2));
+        assert_eq!(diffs.data(), &vec![-1, 1, -1, 2]);
+    }
+
+    #[test]
+    fn test_slice_dim() {
+        // 2x4 frame:
+        // [1, 2, 3, 4]
+        // [5, 6, 7, 8]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(10, 14)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let middle = df.slice_dim::<Dim1>(1, 3);
+
+        assert_eq!(
+            middle.index().indices,
+            hlist![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(11, 13)]
+        );
+        assert_eq!(middle.data(), &vec![2, 3, 6, 7]);
+
+        assert_eq!(df.slice_dim::<Dim1>(2, 2).n_rows(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slice 3..5 out of bounds for a dimension of size 4")]
+    fn test_slice_dim_out_of_bounds() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 1),
+            NumericRangeIndex::<i32>::new(0, 4)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+        let _ = df.slice_dim::<Dim1>(3, 5);
+    }
+
+    #[test]
+    fn test_select_dim() {
+        // 2x3 frame:
+        // [1, 2, 3]
+        // [4, 5, 6]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            CategoricalRange::new(vec!["a", "b", "c"])
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+
+        let picked = df.select_dim::<Dim1>(&[2, 0]);
+
+        assert_eq!(
+            picked.index().indices,
+            hlist![
+                NumericRangeIndex::new(0, 2),
+                CategoricalRange::new(vec!["c", "a"])
+            ]
+        );
+        assert_eq!(picked.data(), &vec![3, 1, 6, 4]);
+
+        let rows = df.select_dim::<Dim0>(&[1, 1]);
+        assert_eq!(rows.index().indices.head, CategoricalRange::new(vec![1, 1]));
+        assert_eq!(rows.data(), &vec![4, 5, 6, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 3 out of bounds for a dimension of size 3")]
+    fn test_select_dim_out_of_bounds() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 1),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3]);
+        let _ = df.select_dim::<Dim1>(&[0, 3]);
+    }
+
+    #[test]
+    fn test_sub_along_dim_column_means() {
+        // 2x3 frame:
+        // [1, 2, 9]
+        // [3, 6, 3]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1.0, 2.0, 9.0, 3.0, 6.0, 3.0]);
+
+        let column_means = df.mean_over_dim::<Dim0>();
+        let centered = df.sub_along_dim::<Dim0>(&column_means);
+
+        assert_eq!(centered.index(), df.index());
+        assert_eq!(centered.data(), &vec![-1.0, -2.0, 3.0, 1.0, 2.0, -3.0]);
+
+        let row_maxima =
+            df.aggregate_over_dim::<Dim1, _, f64>(|row| row.copied().fold(0.0, f64::max));
+        let scaled = df.div_along_dim::<Dim1>(&row_maxima);
+        assert_eq!(
+            scaled.data(),
+            &vec![1.0 / 9.0, 2.0 / 9.0, 1.0, 0.5, 1.0, 0.5]
+        );
+        let restored = scaled.mul_along_dim::<Dim1>(&row_maxima);
+        assert_eq!(restored.data(), df.data());
+        assert_eq!(
+            centered.add_along_dim::<Dim0>(&column_means).data(),
+            df.data()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Broadcast frame index must match the remaining dimensions")]
+    fn test_broadcast_binary_over_dim_mismatched_index() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 2)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+        let other = DataFrame::new(
+            CompoundIndex::new(hlist![NumericRangeIndex::<i32>::new(5, 7)]),
+            vec![1, 1],
+        );
+        let _ = df.sub_along_dim::<Dim0>(&other);
+    }
+
+    #[test]
+    fn test_cumsum_over_dim() {
+        // 2x3 frame:
+        // [1, 2, 3]
+        // [4, 5, 6]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+
+        let along_rows = df.cumsum_over_dim::<Dim1>();
+        assert_eq!(along_rows.index(), df.index());
+        assert_eq!(along_rows.data(), &vec![1, 3, 6, 4, 9, 15]);
+
+        let along_cols = df.cumsum_over_dim::<Dim0>();
+        assert_eq!(along_cols.data(), &vec![1, 2, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_apply_pairs_over_dim() {
+        // 2x4 frame:
+        // [1, 2,  6, 3]
+        // [4, 4, 12, 6]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 4)
+        ]);
+        let df = DataFrame::new(index, vec![1.0, 2.0, 6.0, 3.0, 4.0, 4.0, 12.0, 6.0]);
+
+        let ratios = df.apply_pairs_over_dim::<Dim1, _, _>(|a, b| b / a);
+
+        assert_eq!(
+            ratios.index().indices,
+            hlist![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(1, 4)]
+        );
+        assert_eq!(ratios.data(), &vec![2.0, 3.0, 0.5, 1.0, 3.0, 0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must preserve the slice length")]
+    fn test_apply_along_axis_wrong_length() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 2)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+        let _ = df.apply_along_axis::<Dim1, _, _>(|_| vec![0]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_apply_along_axis_par_matches_sequential() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 50),
+            NumericRangeIndex::<i32>::new(0, 8)
+        ]);
+        let data: Vec<f64> = (0..400).map(|i| ((i * 37) % 101) as f64).collect();
+        let df = DataFrame::new(index, data);
+
+        // Normalize each column to unit maximum
+        let normalize = |col: Vec<f64>| {
+            let max = col.iter().cloned().fold(f64::MIN, f64::max);
+            col.into_iter().map(|v| v / max).collect::<Vec<_>>()
+        };
+        let sequential = df.clone().apply_along_axis::<Dim0, _, _>(normalize);
+        let parallel = df.apply_along_axis_par::<Dim0, _, _>(normalize);
+
+        assert_eq!(parallel.data(), sequential.data());
+    }
+}
@@ -1,9 +1,13 @@
 //! Core DataFrame struct and basic methods.
-use crate::mapped_index::VariableRange;
-use crate::mapped_index::compound_index::CompoundIndex;
-use frunk::HList;
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist, IndexShape};
+use crate::mapped_index::{IntoValues, VariableRange};
+use frunk::{HList, hlist};
 use rand::Rng;
 use rand::seq::IteratorRandom;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::iter::Zip;
 use std::ops::Index;
 
 pub trait FrameData: Index<usize> {
@@ -14,6 +18,30 @@ pub trait FrameData: Index<usize> {
     }
 }
 
+/// Non-panicking counterpart of [`Index<usize>`], for generic code that must handle
+/// out-of-range positions.
+///
+/// Implemented for every [`FrameData`] and for [`DataFrame`].
+///
+/// # Examples
+/// ```
+/// use slice_and_dice::{DataFrame, NumericRangeIndex};
+/// use slice_and_dice::data_frame::core::TryIndex;
+/// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+/// assert_eq!(df.try_index(1), Some(&2));
+/// assert_eq!(df.try_index(2), None);
+/// ```
+pub trait TryIndex: Index<usize> {
+    /// Returns the element at `index`, or `None` if it is out of range.
+    fn try_index(&self, index: usize) -> Option<&Self::Output>;
+}
+
+impl<D: FrameData> TryIndex for D {
+    fn try_index(&self, index: usize) -> Option<&Self::Output> {
+        (index < self.len()).then(|| &self[index])
+    }
+}
+
 /// Macro to allow direct field access for tests and internal code.
 /// This is used to avoid having to update all the direct field accesses in the codebase.
 #[macro_export]
@@ -51,6 +79,60 @@ impl<D> FrameData for Vec<D> {
     }
 }
 
+/// Frame data stored in a boxed slice, which unlike a `Vec` carries no spare capacity.
+///
+/// `Box<[T]>` itself does not implement [`Index`], so it is wrapped to serve as [`FrameData`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoxedSlice<T>(pub Box<[T]>);
+
+impl<T> Index<usize> for BoxedSlice<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T> FrameData for BoxedSlice<T> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Reasons why [`DataFrame::validate`] can reject a frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The index and the data hold a different number of elements.
+    SizeMismatch {
+        /// The size of the index.
+        index_size: usize,
+        /// The length of the data.
+        data_len: usize,
+    },
+    /// The index breaks an invariant of its type; see [`VariableRange::is_well_formed`].
+    MalformedIndex,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::SizeMismatch {
+                index_size,
+                data_len,
+            } => write!(
+                f,
+                "index size {} does not match data length {}",
+                index_size, data_len
+            ),
+            ValidationError::MalformedIndex => write!(f, "index is malformed"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Hash)]
 pub struct DataFrame<I, D>
@@ -158,6 +240,39 @@ where
         self.index.iter().zip(self.data.iter())
     }
 
+    /// Iterate over the index values alone, without borrowing the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(3, 5), vec!['a', 'b']);
+    /// assert_eq!(df.index_values().collect::<Vec<_>>(), vec![3, 4]);
+    /// ```
+    pub fn index_values(&self) -> impl Iterator<Item = I::Value<'_>> + '_ {
+        self.index.iter()
+    }
+
+    /// Iterate over (index_value, &data) pairs for indices whose values do not borrow from
+    /// the index, such as numeric ones.
+    ///
+    /// The labels are the index's owned value type, so they can be collected or moved into owned
+    /// consumers without being tied to the lifetime of the frame's index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(5, 7), vec![1, 2]);
+    /// let labels: Vec<i32> = df.iter_owned().map(|(i, _)| i).collect();
+    /// assert_eq!(labels, vec![5, 6]);
+    /// ```
+    pub fn iter_owned<'a>(&'a self) -> impl Iterator<Item = (I::OwnedValue, &'a D::Output)>
+    where
+        I: IntoValues + VariableRange<Value<'a> = <I as IntoValues>::OwnedValue>,
+        I::OwnedValue: Copy,
+    {
+        self.index.iter().zip(self.data.iter())
+    }
+
     //noinspection RsNeedlessLifetimes
     /// Choose n rows without replacement using the provided RNG.
     /// If n >= length, all rows are returned (without guaranteed order).
@@ -202,6 +317,107 @@ where
     pub fn n_rows(&self) -> usize {
         self.data.len()
     }
+
+    /// Check that the frame is well formed: the index and data have the same size, and the
+    /// index upholds the invariants of its type.
+    ///
+    /// Frames built through [`DataFrame::new`] and safe index constructors always pass;
+    /// this is for frames assembled from their public fields, unchecked constructors or
+    /// deserialized data.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::data_frame::core::ValidationError;
+    /// let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// assert_eq!(df.validate(), Ok(()));
+    /// df.data.push(3);
+    /// assert_eq!(
+    ///     df.validate(),
+    ///     Err(ValidationError::SizeMismatch { index_size: 2, data_len: 3 })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.index.size() != self.data.len() {
+            return Err(ValidationError::SizeMismatch {
+                index_size: self.index.size(),
+                data_len: self.data.len(),
+            });
+        }
+        if !self.index.is_well_formed() {
+            return Err(ValidationError::MalformedIndex);
+        }
+        Ok(())
+    }
+
+    /// Replace the index with another of the same size, keeping the data.
+    ///
+    /// # Panics
+    /// Panics if `index.size()` differs from the number of rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// let shifted = df.with_index(NumericRangeIndex::<i32>::new(10, 12));
+    /// assert_eq!(shifted.iter().next(), Some((10, &1)));
+    /// ```
+    pub fn with_index<I2: VariableRange>(self, index: I2) -> DataFrame<I2, D> {
+        assert_eq!(
+            index.size(),
+            self.n_rows(),
+            "New index size must match the number of rows"
+        );
+        DataFrame::new(index, self.data)
+    }
+
+    /// Wrap the index in a one-dimensional [`CompoundIndex`], so the frame can be used with
+    /// the compound API. The inverse of [`DataFrame::collapse_single_index`].
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// let compound = df.as_compound();
+    /// assert_eq!(compound.index().indices.head, NumericRangeIndex::new(0, 2));
+    /// ```
+    pub fn as_compound(self) -> DataFrame<CompoundIndex<HList![I]>, D> {
+        DataFrame::new(CompoundIndex::new(hlist![self.index]), self.data)
+    }
+
+    /// Panic with a description of the first difference if `other_index` does not hold the
+    /// same sequence of values as this frame's index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// df.assert_aligned(&NumericRangeIndex::<i32>::new(0, 2));
+    /// ```
+    pub fn assert_aligned<'a, 'b, O>(&'a self, other_index: &'b O)
+    where
+        O: VariableRange,
+        I::Value<'a>: PartialEq<O::Value<'b>> + Debug,
+        O::Value<'b>: Debug,
+    {
+        assert_eq!(
+            self.index.size(),
+            other_index.size(),
+            "Indices are not aligned: lengths differ"
+        );
+        let mismatch = self
+            .index
+            .iter()
+            .zip(other_index.iter())
+            .enumerate()
+            .find(|(_, (a, b))| a != b);
+        if let Some((position, (a, b))) = mismatch {
+            panic!(
+                "Indices are not aligned: first difference at position {}: {:?} != {:?}",
+                position, a, b
+            );
+        }
+    }
 }
 
 impl<I, T> DataFrame<I, Vec<T>>
@@ -226,6 +442,24 @@ where
         DataFrame::new(self.index().clone(), data)
     }
 
+    /// Map each element through `map` and fold the results with `reduce`, starting from
+    /// `init`, without materializing the mapped values as [`DataFrame::map`] would.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec!["a", "bc", "def"]);
+    /// let total_len = df.map_reduce(|s| s.len(), 0, |acc, len| acc + len);
+    /// assert_eq!(total_len, 6);
+    /// ```
+    pub fn map_reduce<U, R, MapF, RedF>(&self, map: MapF, init: R, reduce: RedF) -> R
+    where
+        MapF: Fn(&T) -> U,
+        RedF: FnMut(R, U) -> R,
+    {
+        self.data.iter().map(map).fold(init, reduce)
+    }
+
     /// Map each element of the DataFrame's data to a new value, keeping the same index.
     ///
     /// # Examples
@@ -249,6 +483,138 @@ where
         DataFrame::new(self.index().clone(), data)
     }
 
+    /// Map each element through a fallible function, keeping the same index.
+    ///
+    /// Stops at and returns the first error.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 2);
+    /// let df = DataFrame::new(idx, vec!["1", "2"]);
+    /// let parsed = df.try_map(|s| s.parse::<i32>()).unwrap();
+    /// assert_eq!(parsed.data(), &vec![1, 2]);
+    /// ```
+    pub fn try_map<U, E, F>(&self, f: F) -> Result<DataFrame<I, Vec<U>>, E>
+    where
+        F: FnMut(&T) -> Result<U, E>,
+    {
+        let data = self.data().iter().map(f).collect::<Result<_, _>>()?;
+        Ok(DataFrame::new(self.index().clone(), data))
+    }
+
+    /// Clamp each element into the inclusive range `[lo, hi]`, keeping the same index.
+    ///
+    /// # Panics
+    /// Panics if `lo > hi`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 3);
+    /// let df = DataFrame::new(idx, vec![0.5, 1.5, -0.5]);
+    /// assert_eq!(df.clamp(0.0, 1.0).data(), &vec![0.5, 1.0, 0.0]);
+    /// ```
+    pub fn clamp(&self, lo: T, hi: T) -> DataFrame<I, Vec<T>>
+    where
+        T: PartialOrd + Copy,
+    {
+        assert!(lo <= hi, "lo must not be greater than hi");
+        self.map(|&v| {
+            if v < lo {
+                lo
+            } else if v > hi {
+                hi
+            } else {
+                v
+            }
+        })
+    }
+
+    /// Pick each element from `self` where `cond` is true and from `other` otherwise,
+    /// like numpy's `where`.
+    ///
+    /// # Panics
+    /// Panics if the indices of `self`, `cond` and `other` are not all equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 2);
+    /// let a = DataFrame::new(idx.clone(), vec![1, 2]);
+    /// let b = DataFrame::new(idx.clone(), vec![-1, -2]);
+    /// let cond = a.map(|&v| v > 1);
+    /// assert_eq!(a.select_where(&cond, &b).data(), &vec![-1, 2]);
+    /// ```
+    pub fn select_where(
+        &self,
+        cond: &DataFrame<I, Vec<bool>>,
+        other: &DataFrame<I, Vec<T>>,
+    ) -> DataFrame<I, Vec<T>>
+    where
+        I: PartialEq,
+        T: Clone,
+    {
+        assert!(
+            self.index() == cond.index(),
+            "Condition index must match the DataFrame index"
+        );
+        assert!(
+            self.index() == other.index(),
+            "Other index must match the DataFrame index"
+        );
+        let data = cond
+            .data()
+            .iter()
+            .zip(self.data().iter().zip(other.data().iter()))
+            .map(|(&c, (a, b))| if c { a.clone() } else { b.clone() })
+            .collect();
+        DataFrame::new(self.index().clone(), data)
+    }
+
+    /// Combine the elements of `self`, `b` and `c` at each position with `f`.
+    ///
+    /// # Panics
+    /// Panics if the indices of `self`, `b` and `c` are not all equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 2);
+    /// let x = DataFrame::new(idx.clone(), vec![3.0, 5.0]);
+    /// let mean = DataFrame::new(idx.clone(), vec![1.0, 1.0]);
+    /// let std = DataFrame::new(idx.clone(), vec![2.0, 4.0]);
+    /// let z = x.zip3_with(&mean, &std, |x, m, s| (x - m) / s);
+    /// assert_eq!(z.data(), &vec![1.0, 1.0]);
+    /// ```
+    pub fn zip3_with<U, W, V, F>(
+        &self,
+        b: &DataFrame<I, Vec<U>>,
+        c: &DataFrame<I, Vec<W>>,
+        f: F,
+    ) -> DataFrame<I, Vec<V>>
+    where
+        I: PartialEq,
+        F: Fn(&T, &U, &W) -> V,
+    {
+        assert!(
+            self.index() == b.index(),
+            "Index of b must match the DataFrame index"
+        );
+        assert!(
+            self.index() == c.index(),
+            "Index of c must match the DataFrame index"
+        );
+        let data = self
+            .data
+            .iter()
+            .zip(&b.data)
+            .zip(&c.data)
+            .map(|((x, y), z)| f(x, y, z))
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+
     /// Build a DataFrame by mapping each index value to a data value.
     ///
     /// # Examples
@@ -266,6 +632,134 @@ where
         DataFrame::new(index, data)
     }
 
+    /// Overwrite every element with a value computed from its index label, discarding the
+    /// current data; the in-place counterpart of [`DataFrame::build_from_index`].
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![7, 7, 7]);
+    /// df.reset_from_index(|i| i + 1);
+    /// assert_eq!(df.data(), &vec![1, 2, 3]);
+    /// ```
+    pub fn reset_from_index<F>(&mut self, mut f: F)
+    where
+        F: FnMut(I::Value<'_>) -> T,
+    {
+        for (slot, label) in self.data.iter_mut().zip(self.index.iter()) {
+            *slot = f(label);
+        }
+    }
+
+    /// Replace the index with a `CategoricalRange` of labels computed from each index value,
+    /// keeping the data.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 2);
+    /// let df = DataFrame::new(idx, vec![10, 20]);
+    /// let relabeled = df.relabel_to_categorical(|i| format!("row{i}"));
+    /// assert_eq!(relabeled.index().values, vec!["row0", "row1"]);
+    /// assert_eq!(relabeled.data(), &vec![10, 20]);
+    /// ```
+    pub fn relabel_to_categorical<O, F>(&self, f: F) -> DataFrame<CategoricalRange<O>, Vec<T>>
+    where
+        O: Sync + Clone,
+        T: Clone,
+        F: FnMut(I::Value<'_>) -> O,
+    {
+        let labels = self.index().iter().map(f).collect();
+        DataFrame::new(CategoricalRange::new(labels), self.data().clone())
+    }
+
+    /// Collect the (index_value, &data) pairs of [`DataFrame::iter`] into a Vec.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec!['a', 'b']);
+    /// assert_eq!(df.to_pairs(), vec![(0, &'a'), (1, &'b')]);
+    /// ```
+    pub fn to_pairs(&self) -> Vec<(I::Value<'_>, &T)> {
+        self.iter().collect()
+    }
+
+    /// Like [`DataFrame::to_pairs`], but sorted by value in ascending order. The sort is
+    /// stable, so equal values keep their index order.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::DataFrame;
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// let df = DataFrame::new(CategoricalRange::new(vec!["a", "b"]), vec![2, 1]);
+    /// assert_eq!(df.sorted_pairs_by_value(), vec![(&"b", &1), (&"a", &2)]);
+    /// ```
+    pub fn sorted_pairs_by_value(&self) -> Vec<(I::Value<'_>, &T)>
+    where
+        T: Ord,
+    {
+        let mut pairs = self.to_pairs();
+        pairs.sort_by_key(|(_, value)| *value);
+        pairs
+    }
+
+    /// Consume the frame into a Vec of (index_value, data) pairs, with owned labels.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec!['a', 'b']);
+    /// assert_eq!(df.into_pairs(), vec![(0, 'a'), (1, 'b')]);
+    /// ```
+    pub fn into_pairs(self) -> Vec<(I::OwnedValue, T)>
+    where
+        I: IntoValues,
+    {
+        self.into_iter().collect()
+    }
+
+    /// Convert the data into a boxed slice, dropping any spare capacity of the `Vec`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let mut data = Vec::with_capacity(100);
+    /// data.extend([1, 2]);
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), data).into_boxed_slice();
+    /// assert_eq!(&*df.data().0, &[1, 2]);
+    /// ```
+    pub fn into_boxed_slice(self) -> DataFrame<I, BoxedSlice<T>> {
+        DataFrame::new(self.index, BoxedSlice(self.data.into_boxed_slice()))
+    }
+
+    /// Returns the data as a contiguous slice.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 3);
+    /// let df = DataFrame::new(idx, vec![1, 2, 3]);
+    /// assert_eq!(df.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns the data as a contiguous mutable slice.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 3);
+    /// let mut df = DataFrame::new(idx, vec![1, 2, 3]);
+    /// df.as_mut_slice()[1] = 20;
+    /// assert_eq!(df.data(), &vec![1, 20, 3]);
+    /// ```
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+
     #[cfg(feature = "rayon")]
     pub fn build_from_index_par<F>(index: I, f: F) -> DataFrame<I, Vec<T>>
     where
@@ -284,6 +778,25 @@ where
             .collect();
         DataFrame::new(index, data)
     }
+
+    /// Parallel version of [`DataFrame::map_with_key`], mapping the rows on the rayon
+    /// thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn map_with_key_par<U, F>(&self, f: F) -> DataFrame<I, Vec<U>>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(I::Value<'_>, &T) -> U + Sync,
+    {
+        use rayon::prelude::*;
+        let data: Vec<U> = self
+            .data
+            .par_iter()
+            .enumerate()
+            .map(|(i, v)| f(self.index.unflatten_index_value(i), v))
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
 }
 
 impl<I, D> DataFrame<CompoundIndex<HList![I]>, D>
@@ -296,6 +809,87 @@ where
     }
 }
 
+impl<Indices, T> DataFrame<CompoundIndex<Indices>, Vec<T>>
+where
+    Indices: IndexHlist,
+{
+    /// Map each value to a new value, also passing its position along each dimension,
+    /// outermost first.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(5, 7),
+    ///     NumericRangeIndex::<i32>::new(5, 7)
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 1, 1, 1]);
+    /// let diagonal = df.map_with_coords(|coords, v| if coords[0] == coords[1] { *v } else { 0 });
+    /// assert_eq!(diagonal.data(), &vec![1, 0, 0, 1]);
+    /// ```
+    pub fn map_with_coords<U, F>(&self, mut f: F) -> DataFrame<CompoundIndex<Indices>, Vec<U>>
+    where
+        Indices: IndexShape,
+        F: FnMut(&[usize], &T) -> U,
+    {
+        let data = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| f(&self.index.unflatten_coords(i), v))
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+
+    /// Export the frame in long format, as `(coords, value)` records in flat order, where
+    /// `coords` holds the position along each dimension, outermost first.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(0, 2)
+    /// ]);
+    /// let df = DataFrame::new(index, vec!['a', 'b']);
+    /// assert_eq!(df.to_coord_records(), vec![(vec![0, 0], &'a'), (vec![0, 1], &'b')]);
+    /// ```
+    pub fn to_coord_records(&self) -> Vec<(Vec<usize>, &T)>
+    where
+        Indices: IndexShape,
+    {
+        self.index.iter_coords().zip(&self.data).collect()
+    }
+}
+
+impl<T, D> DataFrame<CategoricalRange<T>, D>
+where
+    T: Sync + Clone + Eq + Hash,
+    D: FrameData,
+{
+    /// Panic if the categorical index contains any label more than once.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::DataFrame;
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// let df = DataFrame::new(CategoricalRange::new(vec!["x", "y"]), vec![1, 2]);
+    /// df.assert_unique_index();
+    /// ```
+    pub fn assert_unique_index(&self) {
+        assert!(
+            !self.index.has_duplicates(),
+            "Index contains duplicate labels"
+        );
+    }
+}
+
 impl<I, D> Index<usize> for DataFrame<I, D>
 where
     I: VariableRange,
@@ -308,6 +902,38 @@ where
     }
 }
 
+impl<I, D> TryIndex for DataFrame<I, D>
+where
+    I: VariableRange,
+    D: FrameData,
+{
+    fn try_index(&self, index: usize) -> Option<&Self::Output> {
+        self.data.try_index(index)
+    }
+}
+
+/// Consume the DataFrame, yielding (owned index value, value) pairs.
+///
+/// # Examples
+/// ```
+/// use slice_and_dice::{DataFrame, NumericRangeIndex};
+/// let idx = NumericRangeIndex::<i32>::new(0, 2);
+/// let df = DataFrame::new(idx, vec![String::from("a"), String::from("b")]);
+/// let pairs: Vec<(i32, String)> = df.into_iter().collect();
+/// assert_eq!(pairs, vec![(0, "a".to_string()), (1, "b".to_string())]);
+/// ```
+impl<I, T> IntoIterator for DataFrame<I, Vec<T>>
+where
+    I: IntoValues,
+{
+    type Item = (I::OwnedValue, T);
+    type IntoIter = Zip<I::IntoValues, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.index.into_values().zip(self.data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +1038,97 @@ mod tests {
         assert_eq!(string_df[2], "30");
     }
 
+    #[test]
+    fn test_map_reduce() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1_i32, 2, 3]);
+
+        let sum_of_squares = df.map_reduce(|v| v * v, 0, |acc, sq| acc + sq);
+        assert_eq!(sum_of_squares, 14);
+
+        let max_abs = df.map_reduce(|v| (v - 3).abs(), 0, i32::max);
+        assert_eq!(max_abs, 2);
+    }
+
+    #[test]
+    fn test_try_map() {
+        let index = NumericRangeIndex::<i32>::new(0, 3);
+        let df = DataFrame::new(index.clone(), vec!["1", "22", "333"]);
+
+        let parsed = df.try_map(|s| s.parse::<u32>()).unwrap();
+        assert_eq!(parsed.index(), &index);
+        assert_eq!(parsed.data(), &vec![1, 22, 333]);
+
+        let bad = DataFrame::new(index, vec!["1", "x", "-3"]);
+        let mut calls = 0;
+        let result = bad.try_map(|s| {
+            calls += 1;
+            s.parse::<u32>()
+        });
+        assert!(result.is_err());
+        // Short-circuits on the first error
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let index = NumericRangeIndex::<i32>::new(0, 3);
+        let df = DataFrame::new(index.clone(), vec![-5, 0, 10]);
+
+        let clamped = df.clamp(0, 5);
+        assert_eq!(clamped.index(), &index);
+        assert_eq!(clamped.data(), &vec![0, 0, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "lo must not be greater than hi")]
+    fn test_clamp_inverted_bounds() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 1), vec![1]);
+        let _ = df.clamp(5, 0);
+    }
+
+    #[test]
+    fn test_select_where() {
+        let index = NumericRangeIndex::<i32>::new(0, 3);
+        let a = DataFrame::new(index.clone(), vec![1, 2, 3]);
+        let b = DataFrame::new(index.clone(), vec![10, 20, 30]);
+        let cond = DataFrame::new(index.clone(), vec![true, false, true]);
+
+        let selected = a.select_where(&cond, &b);
+        assert_eq!(selected.index(), &index);
+        assert_eq!(selected.data(), &vec![1, 20, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Other index must match the DataFrame index")]
+    fn test_select_where_mismatched_index() {
+        let a = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+        let cond = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![true, false]);
+        let b = DataFrame::new(NumericRangeIndex::<i32>::new(1, 3), vec![10, 20]);
+        let _ = a.select_where(&cond, &b);
+    }
+
+    #[test]
+    fn test_zip3_with() {
+        let index = NumericRangeIndex::<i32>::new(0, 3);
+        let a = DataFrame::new(index.clone(), vec![1, 2, 3]);
+        let b = DataFrame::new(index.clone(), vec![10.0, 20.0, 30.0]);
+        let c = DataFrame::new(index.clone(), vec!["x", "y", "z"]);
+
+        let combined = a.zip3_with(&b, &c, |&a, &b, c| format!("{}{}", c, a as f64 + b));
+
+        assert_eq!(combined.index(), &index);
+        assert_eq!(combined.data(), &vec!["x11", "y22", "z33"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index of c must match the DataFrame index")]
+    fn test_zip3_with_mismatched_index() {
+        let a = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+        let b = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+        let c = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+        let _ = a.zip3_with(&b, &c, |a, b, c| a + b + c);
+    }
+
     #[test]
     fn test_build_from_index() {
         let index = NumericRangeIndex::<i32>::new(0, 3); // [0, 1, 2]
@@ -425,6 +1142,270 @@ mod tests {
         assert_eq!(df[2], 4);
     }
 
+    #[test]
+    fn test_relabel_to_categorical() {
+        let index = NumericRangeIndex::<i32>::new(0, 3); // [0, 1, 2]
+        let df = DataFrame::new(index, vec![10, 20, 30]);
+
+        let relabeled = df.relabel_to_categorical(|i| format!("row{}", i));
+
+        assert_eq!(
+            relabeled.index(),
+            &CategoricalRange::new(vec![
+                "row0".to_string(),
+                "row1".to_string(),
+                "row2".to_string()
+            ])
+        );
+        assert_eq!(relabeled.data(), &vec![10, 20, 30]);
+        let pairs: Vec<(&String, &i32)> = relabeled.iter().collect();
+        assert_eq!(pairs[1], (&"row1".to_string(), &20));
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let index = NumericRangeIndex::<i32>::new(0, 3); // [0, 1, 2]
+        let mut df = DataFrame::new(index, vec![10, 20, 30]);
+
+        assert_eq!(df.as_slice(), &[10, 20, 30]);
+
+        // Mutation through the mutable slice is visible in the frame
+        df.as_mut_slice()[2] = 300;
+        assert_eq!(df.as_slice(), &[10, 20, 300]);
+        assert_eq!(df[2], 300);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_map_with_key_par_matches_sequential() {
+        let df = DataFrame::build_from_index(NumericRangeIndex::<i64>::new(0, 10_000), |i| i % 7);
+
+        let sequential = df.map_with_key(|i, v| i * 3 + v);
+        let parallel = df.map_with_key_par(|i, v| i * 3 + v);
+
+        assert_eq!(parallel.data(), sequential.data());
+    }
+
+    #[test]
+    fn test_reset_from_index() {
+        let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![9, 9, 9, 9]);
+
+        df.reset_from_index(|i| i * i);
+
+        assert_eq!(df.index(), &NumericRangeIndex::new(0, 4));
+        assert_eq!(df.data(), &vec![0, 1, 4, 9]);
+    }
+
+    #[test]
+    fn test_to_pairs() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(3, 6), vec![30, 40, 50]);
+
+        assert_eq!(df.to_pairs(), vec![(3, &30), (4, &40), (5, &50)]);
+        assert_eq!(df.into_pairs(), vec![(3, 30), (4, 40), (5, 50)]);
+    }
+
+    #[test]
+    fn test_sorted_pairs_by_value() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![30, 10, 20, 10]);
+
+        let sorted = df.sorted_pairs_by_value();
+
+        assert_eq!(sorted, vec![(1, &10), (3, &10), (2, &20), (0, &30)]);
+    }
+
+    #[test]
+    fn test_index_values() {
+        let numeric = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1.0, 2.0, 3.0]);
+        assert_eq!(numeric.index_values().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let categorical = DataFrame::new(CategoricalRange::new(vec!["x", "y"]), vec![1, 2]);
+        assert_eq!(
+            categorical.index_values().collect::<Vec<_>>(),
+            vec![&"x", &"y"]
+        );
+    }
+
+    #[test]
+    fn test_iter_owned() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        let pairs: Vec<(i32, &i32)> = df.iter_owned().collect();
+
+        assert_eq!(pairs, vec![(0, &10), (1, &20), (2, &30)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index contains duplicate labels")]
+    fn test_assert_unique_index() {
+        let df = DataFrame::new(CategoricalRange::new(vec!["a", "b", "a"]), vec![1, 2, 3]);
+        df.assert_unique_index();
+    }
+
+    #[test]
+    fn test_into_boxed_slice() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        let boxed = df.clone().into_boxed_slice();
+
+        assert_eq!(boxed.index(), df.index());
+        assert_eq!(&*boxed.data().0, df.as_slice());
+        assert_eq!(boxed[1], 20);
+        assert_eq!(
+            boxed.iter().collect::<Vec<_>>(),
+            df.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_try_index() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        assert_eq!(df.try_index(0), Some(&10));
+        assert_eq!(df.try_index(2), Some(&30));
+        assert_eq!(df.try_index(3), None);
+        assert_eq!(df.data().try_index(3), None);
+    }
+
+    #[test]
+    fn test_as_compound_round_trip() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        let compound = df.clone().as_compound();
+        assert_eq!(compound.n_rows(), 3);
+        assert_eq!(
+            compound.iter().next().map(|(v, x)| (v.head, *x)),
+            Some((0, 10))
+        );
+
+        let collapsed = compound.collapse_single_index();
+        assert_eq!(collapsed.index(), df.index());
+        assert_eq!(collapsed.data(), df.data());
+    }
+
+    #[test]
+    fn test_with_index() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        let labelled = df.with_index(CategoricalRange::new(vec!["a", "b", "c"]));
+
+        assert_eq!(
+            labelled.index(),
+            &CategoricalRange::new(vec!["a", "b", "c"])
+        );
+        assert_eq!(labelled.data(), &vec![10, 20, 30]);
+    }
+
+    #[test]
+    #[should_panic(expected = "New index size must match the number of rows")]
+    fn test_with_index_size_mismatch() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+        let _ = df.with_index(CategoricalRange::new(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn test_assert_aligned() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+        df.assert_aligned(&NumericRangeIndex::<i32>::new(0, 3));
+        df.assert_aligned(&crate::SparseNumericIndex::<i32>::new(vec![0, 1, 2].into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "first difference at position 1: 1 != 5")]
+    fn test_assert_aligned_mismatch() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+        // Differs at positions 1 and 2; the first is reported
+        df.assert_aligned(&crate::SparseNumericIndex::<i32>::new(vec![0, 5, 6].into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "lengths differ")]
+    fn test_assert_aligned_length_mismatch() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+        df.assert_aligned(&NumericRangeIndex::<i32>::new(0, 2));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let index = NumericRangeIndex::<i32>::new(5, 8);
+        let df = DataFrame::new(index, vec![vec![1], vec![2, 2], vec![3, 3, 3]]);
+
+        let pairs: Vec<(i32, Vec<i32>)> = df.into_iter().collect();
+        assert_eq!(
+            pairs,
+            vec![(5, vec![1]), (6, vec![2, 2]), (7, vec![3, 3, 3])]
+        );
+
+        let labels = CategoricalRange::new(vec!["x".to_string(), "y".to_string()]);
+        let df = DataFrame::new(labels, vec![1, 2]);
+        let pairs: Vec<(String, i32)> = df.into_iter().collect();
+        assert_eq!(pairs, vec![("x".to_string(), 1), ("y".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_map_with_coords() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            CategoricalRange::new(vec!["a", "b", "c"])
+        ]);
+        let df = DataFrame::new(index, vec![0; 6]);
+
+        let labelled = df.map_with_coords(|coords, v| v + coords[0] * 10 + coords[1]);
+
+        assert_eq!(labelled.index(), df.index());
+        assert_eq!(labelled.data(), &vec![0, 1, 2, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+        assert_eq!(df.validate(), Ok(()));
+
+        let short = DataFrame {
+            index: NumericRangeIndex::<i32>::new(0, 3),
+            data: vec![1, 2],
+        };
+        assert_eq!(
+            short.validate(),
+            Err(ValidationError::SizeMismatch {
+                index_size: 3,
+                data_len: 2
+            })
+        );
+
+        let unsorted =
+            unsafe { crate::SparseNumericIndex::<i32>::from_sorted_unchecked(vec![3, 1]) };
+        let corrupt = DataFrame::new(unsorted.clone(), vec![1, 2]);
+        assert_eq!(corrupt.validate(), Err(ValidationError::MalformedIndex));
+
+        // Malformed sub-indices are found inside compound indices too
+        let nested = DataFrame::new(
+            CompoundIndex::new(hlist![NumericRangeIndex::<i32>::new(0, 1), unsorted]),
+            vec![1, 2],
+        );
+        assert_eq!(nested.validate(), Err(ValidationError::MalformedIndex));
+    }
+
+    #[test]
+    fn test_to_coord_records() {
+        let index = CompoundIndex::new(hlist![
+            CategoricalRange::new(vec!["x", "y"]),
+            NumericRangeIndex::<i32>::new(10, 12)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+
+        let records = df.to_coord_records();
+
+        assert_eq!(
+            records,
+            vec![
+                (vec![0, 0], &1),
+                (vec![0, 1], &2),
+                (vec![1, 0], &3),
+                (vec![1, 1], &4)
+            ]
+        );
+    }
+
     #[test]
     fn test_collapse_single_index() {
         // Create a compound index with a single NumericRangeIndex
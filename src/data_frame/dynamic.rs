@@ -0,0 +1,182 @@
+//! Operations on DataFrames with a runtime-typed [`DynCompoundIndex`], addressing axes by
+//! number rather than by type-level dimension markers.
+use super::core::DataFrame;
+use crate::mapped_index::VariableRange;
+use crate::mapped_index::dyn_index::DynCompoundIndex;
+use std::iter::Sum;
+
+impl<T> DataFrame<DynCompoundIndex, Vec<T>> {
+    /// Construct a DataFrame over a runtime-shaped index, checking that the data holds one
+    /// value per position, i.e. the product of the axis sizes.
+    ///
+    /// # Panics
+    /// Panics if the data length does not match the shape of the index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::dyn_index::{DynCompoundIndex, DynIndex};
+    ///
+    /// let index = DynCompoundIndex::new(vec![DynIndex::Numeric(NumericRangeIndex::new(0, 2))]);
+    /// let df = DataFrame::new_dyn(index, vec![1, 2]);
+    /// assert_eq!(df.at_coords(&[1]), &2);
+    /// ```
+    pub fn new_dyn(index: DynCompoundIndex, data: Vec<T>) -> Self {
+        let shape: Vec<usize> = index.indices.iter().map(|idx| idx.size()).collect();
+        assert_eq!(
+            index.size(),
+            data.len(),
+            "Data length does not match the index shape {:?}",
+            shape
+        );
+        DataFrame::new(index, data)
+    }
+
+    /// The value at the given position on each axis, outermost first.
+    ///
+    /// # Panics
+    /// Panics if the number of coordinates differs from the number of axes, or any
+    /// coordinate is out of bounds for its axis.
+    pub fn at_coords(&self, coords: &[usize]) -> &T {
+        let indices = &self.index.indices;
+        assert_eq!(
+            coords.len(),
+            indices.len(),
+            "Expected one coordinate per axis"
+        );
+        let flat = coords
+            .iter()
+            .zip(indices)
+            .enumerate()
+            .fold(0, |flat, (axis, (&coord, idx))| {
+                assert!(
+                    coord < idx.size(),
+                    "Coordinate {} out of bounds for axis {} of size {}",
+                    coord,
+                    axis,
+                    idx.size()
+                );
+                flat * idx.size() + coord
+            });
+        &self.data[flat]
+    }
+
+    /// Sum over the given axis, removing it from the index.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::dyn_index::{DynCompoundIndex, DynIndex};
+    ///
+    /// let index = DynCompoundIndex::new(vec![
+    ///     DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+    ///     DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+    /// assert_eq!(df.sum_axis(0).data(), &vec![4, 6]);
+    /// ```
+    pub fn sum_axis(self, axis: usize) -> DataFrame<DynCompoundIndex, Vec<T>>
+    where
+        T: for<'a> Sum<&'a T>,
+    {
+        let mut indices = self.index.indices;
+        assert!(
+            axis < indices.len(),
+            "Axis {} out of bounds for a {}-dimensional frame",
+            axis,
+            indices.len()
+        );
+        let m_size = indices[axis].size();
+        let l_size: usize = indices[..axis].iter().map(|idx| idx.size()).product();
+        let r_size: usize = indices[axis + 1..].iter().map(|idx| idx.size()).product();
+
+        let data = (0..l_size)
+            .flat_map(|l_i| (0..r_size).map(move |r_i| l_i * m_size * r_size + r_i))
+            .map(|base| (0..m_size).map(|m_i| &self.data[base + m_i * r_size]).sum())
+            .collect();
+        indices.remove(axis);
+        DataFrame::new(DynCompoundIndex::new(indices), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::categorical_index::CategoricalRange;
+    use crate::mapped_index::dyn_index::{DynIndex, DynValue};
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+    #[test]
+    fn test_new_dyn_and_at_coords() {
+        let index = DynCompoundIndex::new(vec![
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+            DynIndex::Categorical(CategoricalRange::new(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+            ])),
+        ]);
+        let df = DataFrame::new_dyn(index, vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(df.at_coords(&[0, 0]), &1);
+        assert_eq!(df.at_coords(&[0, 2]), &3);
+        assert_eq!(df.at_coords(&[1, 1]), &5);
+        assert_eq!(df.at_coords(&[1, 2]), &6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Data length does not match the index shape [2, 3]")]
+    fn test_new_dyn_shape_mismatch() {
+        let index = DynCompoundIndex::new(vec![
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+            DynIndex::Numeric(NumericRangeIndex::new(0, 3)),
+        ]);
+        let _ = DataFrame::new_dyn(index, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for axis 1")]
+    fn test_at_coords_out_of_bounds() {
+        let index = DynCompoundIndex::new(vec![
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+        ]);
+        let df = DataFrame::new_dyn(index, vec![1, 2, 3, 4]);
+        let _ = df.at_coords(&[0, 2]);
+    }
+
+    #[test]
+    fn test_sum_axis() {
+        // 2x3 frame:
+        // [1, 2, 3]
+        // [4, 5, 6]
+        let rows = DynIndex::Categorical(CategoricalRange::new(vec![
+            "x".to_string(),
+            "y".to_string(),
+        ]));
+        let cols = DynIndex::Numeric(NumericRangeIndex::new(10, 13));
+        let index = DynCompoundIndex::new(vec![rows.clone(), cols.clone()]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+
+        let by_row = df.clone().sum_axis(1);
+        assert_eq!(by_row.index(), &DynCompoundIndex::new(vec![rows]));
+        assert_eq!(by_row.data(), &vec![6, 15]);
+        let labels: Vec<DynValue> = by_row.index().iter().map(|v| v.get(0)).collect();
+        assert_eq!(labels, vec![DynValue::Str("x"), DynValue::Str("y")]);
+
+        let by_col = df.sum_axis(0);
+        assert_eq!(by_col.index(), &DynCompoundIndex::new(vec![cols]));
+        assert_eq!(by_col.data(), &vec![5, 7, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Axis 2 out of bounds")]
+    fn test_sum_axis_out_of_bounds() {
+        let index = DynCompoundIndex::new(vec![DynIndex::Numeric(NumericRangeIndex::new(0, 2))]);
+        let df = DataFrame::new(index, vec![1.0, 2.0]);
+        let _ = df.sum_axis(2);
+    }
+}
@@ -0,0 +1,294 @@
+//! Pluggable policies for filling in values that are missing from a sparse DataFrame.
+use super::core::{DataFrame, FrameData};
+use super::stack::{ExtrapolationMethod, InterpolationMethod};
+use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+use std::ops::Sub;
+
+/// The neighbourhood of a key that has no value, as seen by a [`FillPolicy`].
+#[derive(Debug)]
+pub struct FillContext<'a, I, T> {
+    /// The key for which a value is missing.
+    pub key: I,
+    /// The closest present key before `key`, together with its value.
+    pub previous: Option<(I, &'a T)>,
+    /// The closest present key after `key`, together with its value.
+    pub next: Option<(I, &'a T)>,
+    /// The fallback value supplied by the caller.
+    pub default: &'a T,
+}
+
+impl<'a, I: Ord + Copy, T> FillContext<'a, I, T> {
+    /// Returns true if `key` lies outside the present keys (or there are none).
+    pub fn is_extrapolation(&self) -> bool {
+        self.previous.is_none() || self.next.is_none()
+    }
+
+    /// The value of the nearest present key, preferring the previous one on ties.
+    ///
+    /// Falls back to `default` when there are no present keys at all.
+    pub fn nearest(&self) -> &'a T
+    where
+        I: Sub<Output = I>,
+    {
+        match (self.previous, self.next) {
+            (Some((prev_key, prev)), Some((next_key, next))) => {
+                // Both differences are non-negative since prev_key < key < next_key.
+                if self.key - prev_key <= next_key - self.key {
+                    prev
+                } else {
+                    next
+                }
+            }
+            (Some((_, prev)), None) => prev,
+            (None, Some((_, next))) => next,
+            (None, None) => self.default,
+        }
+    }
+
+    /// The value of the previous present key, or `default` if there is none.
+    pub fn previous_or_default(&self) -> &'a T {
+        self.previous.map_or(self.default, |(_, v)| v)
+    }
+
+    /// The value of the next present key, or `default` if there is none.
+    pub fn next_or_default(&self) -> &'a T {
+        self.next.map_or(self.default, |(_, v)| v)
+    }
+}
+
+/// A strategy for producing a value at a key that has no data.
+///
+/// # Examples
+/// ```
+/// use slice_and_dice::data_frame::fill::{FillContext, FillPolicy};
+///
+/// /// Linearly blends the neighbouring values by position.
+/// struct Midpoint;
+///
+/// impl FillPolicy<i64, f64> for Midpoint {
+///     fn fill(&self, context: FillContext<'_, i64, f64>) -> f64 {
+///         match (context.previous, context.next) {
+///             (Some((_, a)), Some((_, b))) => (a + b) / 2.0,
+///             _ => *context.default,
+///         }
+///     }
+/// }
+/// ```
+pub trait FillPolicy<I, T> {
+    /// Produce the value for the missing key described by `context`.
+    fn fill(&self, context: FillContext<'_, I, T>) -> T;
+}
+
+/// Use the nearest present value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NearestFill;
+
+/// Use the previous present value, or the default if there is none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PreviousFill;
+
+/// Use the next present value, or the default if there is none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NextFill;
+
+/// Always use the default value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DefaultFill;
+
+/// Use one policy for keys between present keys and another for keys beyond either end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitFill<P, E> {
+    /// The policy used when the key has present neighbours on both sides.
+    pub interpolation: P,
+    /// The policy used when the key is before the first or after the last present key.
+    pub extrapolation: E,
+}
+
+impl<I: Ord + Copy + Sub<Output = I>, T: Clone> FillPolicy<I, T> for NearestFill {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        context.nearest().clone()
+    }
+}
+
+impl<I: Ord + Copy, T: Clone> FillPolicy<I, T> for PreviousFill {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        context.previous_or_default().clone()
+    }
+}
+
+impl<I: Ord + Copy, T: Clone> FillPolicy<I, T> for NextFill {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        context.next_or_default().clone()
+    }
+}
+
+impl<I, T: Clone> FillPolicy<I, T> for DefaultFill {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        context.default.clone()
+    }
+}
+
+impl<I: Ord + Copy, T, P, E> FillPolicy<I, T> for SplitFill<P, E>
+where
+    P: FillPolicy<I, T>,
+    E: FillPolicy<I, T>,
+{
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        if context.is_extrapolation() {
+            self.extrapolation.fill(context)
+        } else {
+            self.interpolation.fill(context)
+        }
+    }
+}
+
+impl InterpolationMethod {
+    /// The present (or default) value this method fills the missing key in `context` with.
+    pub(crate) fn select<'a, I, T>(&self, context: &FillContext<'a, I, T>) -> &'a T
+    where
+        I: Ord + Copy + Sub<Output = I>,
+    {
+        match self {
+            InterpolationMethod::Nearest => context.nearest(),
+            InterpolationMethod::Previous => context.previous_or_default(),
+            InterpolationMethod::Next => context.next_or_default(),
+            InterpolationMethod::Default => context.default,
+        }
+    }
+}
+
+impl<I: Ord + Copy + Sub<Output = I>, T: Clone> FillPolicy<I, T> for InterpolationMethod {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        self.select(&context).clone()
+    }
+}
+
+impl<I: Ord + Copy + Sub<Output = I>, T: Clone> FillPolicy<I, T> for ExtrapolationMethod {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        match self {
+            ExtrapolationMethod::Nearest => NearestFill.fill(context),
+            ExtrapolationMethod::Default => DefaultFill.fill(context),
+        }
+    }
+}
+
+/// Look up `key` in a sparse frame, returning its value if present, or otherwise the
+/// neighbourhood a [`FillPolicy`] needs to fill it in.
+pub(crate) fn lookup_sparse<'a, I, D>(
+    df: &'a DataFrame<SparseNumericIndex<I>, D>,
+    key: I,
+    default: &'a D::Output,
+) -> Result<&'a D::Output, FillContext<'a, I, D::Output>>
+where
+    I: Copy + Ord + 'static + Sync,
+    D: FrameData,
+    D::Output: Sized,
+{
+    let df_indices = &df.index().indices;
+    match df_indices.binary_search(&key) {
+        Ok(pos) => Ok(df.data_at(pos)),
+        Err(insert_pos) => {
+            let previous =
+                (insert_pos > 0).then(|| (df_indices[insert_pos - 1], df.data_at(insert_pos - 1)));
+            let next = (insert_pos < df_indices.len())
+                .then(|| (df_indices[insert_pos], df.data_at(insert_pos)));
+            Err(FillContext {
+                key,
+                previous,
+                next,
+                default,
+            })
+        }
+    }
+}
+
+/// Look up each of `keys` in a sparse frame, filling absent keys using `policy`.
+pub(crate) fn fill_sparse<'a, I, D, P>(
+    df: &'a DataFrame<SparseNumericIndex<I>, D>,
+    keys: impl IntoIterator<Item = I> + 'a,
+    policy: &'a P,
+    default: &'a D::Output,
+) -> impl Iterator<Item = D::Output> + 'a
+where
+    I: Copy + Ord + 'static + Sync,
+    D: FrameData,
+    D::Output: Clone,
+    P: FillPolicy<I, D::Output>,
+{
+    keys.into_iter()
+        .map(move |key| match lookup_sparse(df, key, default) {
+            Ok(value) => value.clone(),
+            Err(context) => policy.fill(context),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(
+        key: i32,
+        previous: Option<(i32, &'a i32)>,
+        next: Option<(i32, &'a i32)>,
+        default: &'a i32,
+    ) -> FillContext<'a, i32, i32> {
+        FillContext {
+            key,
+            previous,
+            next,
+            default,
+        }
+    }
+
+    #[test]
+    fn test_builtin_policies_interpolation() {
+        let (a, b, d) = (10, 30, -1);
+        let ctx = || context(2, Some((1, &a)), Some((3, &b)), &d);
+
+        assert!(!ctx().is_extrapolation());
+        assert_eq!(NearestFill.fill(ctx()), 10);
+        assert_eq!(PreviousFill.fill(ctx()), 10);
+        assert_eq!(NextFill.fill(ctx()), 30);
+        assert_eq!(DefaultFill.fill(ctx()), -1);
+    }
+
+    #[test]
+    fn test_nearest_uses_distance() {
+        let (a, b, d) = (10, 30, -1);
+        let closer_to_next = context(4, Some((0, &a)), Some((5, &b)), &d);
+        let closer_to_prev = context(1, Some((0, &a)), Some((5, &b)), &d);
+
+        assert_eq!(NearestFill.fill(closer_to_next), 30);
+        assert_eq!(NearestFill.fill(closer_to_prev), 10);
+    }
+
+    #[test]
+    fn test_builtin_policies_extrapolation() {
+        let (a, d) = (10, -1);
+        let before = || context(0, None, Some((1, &a)), &d);
+        let after = || context(5, Some((1, &a)), None, &d);
+        let empty = || context(5, None, None, &d);
+
+        assert!(before().is_extrapolation());
+        assert_eq!(NearestFill.fill(before()), 10);
+        assert_eq!(NearestFill.fill(after()), 10);
+        assert_eq!(NearestFill.fill(empty()), -1);
+        assert_eq!(PreviousFill.fill(before()), -1);
+        assert_eq!(NextFill.fill(after()), -1);
+    }
+
+    #[test]
+    fn test_split_fill() {
+        let (a, b, d) = (10, 30, -1);
+        let policy = SplitFill {
+            interpolation: NextFill,
+            extrapolation: DefaultFill,
+        };
+
+        assert_eq!(
+            policy.fill(context(2, Some((1, &a)), Some((3, &b)), &d)),
+            30
+        );
+        assert_eq!(policy.fill(context(4, Some((3, &b)), None, &d)), -1);
+    }
+}
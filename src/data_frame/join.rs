@@ -0,0 +1,278 @@
+//! Combining DataFrames by aligning their index labels.
+use super::core::DataFrame;
+use crate::mapped_index::VariableRange;
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::compound_index::CompoundIndex;
+use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+use frunk::{HList, hlist};
+use sorted_vec::SortedSet;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Two sparse frames reindexed onto the union of their keys, as returned by
+/// [`DataFrame::align`].
+pub type Aligned<I, T, U> = (
+    DataFrame<SparseNumericIndex<I>, Vec<Option<T>>>,
+    DataFrame<SparseNumericIndex<I>, Vec<Option<U>>>,
+);
+
+/// The results of combining every row of one frame with every row of another, as returned
+/// by [`DataFrame::pairwise_map`].
+pub type PairwiseFrame<I, J, V> = DataFrame<CompoundIndex<HList![I, J]>, Vec<V>>;
+
+impl<K, T> DataFrame<CategoricalRange<K>, Vec<T>>
+where
+    K: Eq + Hash + Clone + Sync,
+{
+    /// Combine the values of two categorical frames that share a label, in the order of
+    /// the labels in `self`. Labels present in only one of the frames are dropped.
+    ///
+    /// If `other` contains a label more than once, its first occurrence is used.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::DataFrame;
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    ///
+    /// let a = DataFrame::new(CategoricalRange::new(vec!["x", "y"]), vec![1, 2]);
+    /// let b = DataFrame::new(CategoricalRange::new(vec!["y", "z"]), vec![10, 20]);
+    /// let joined = a.inner_join_categorical(&b, |l, r| l + r);
+    /// assert_eq!(joined.index().values, vec!["y"]);
+    /// assert_eq!(joined.data(), &vec![12]);
+    /// ```
+    pub fn inner_join_categorical<U, V, F>(
+        &self,
+        other: &DataFrame<CategoricalRange<K>, Vec<U>>,
+        mut combine: F,
+    ) -> DataFrame<CategoricalRange<K>, Vec<V>>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        let mut lookup = HashMap::with_capacity(other.n_rows());
+        for (label, value) in other.iter() {
+            lookup.entry(label).or_insert(value);
+        }
+
+        let (labels, data) = self
+            .iter()
+            .filter_map(|(label, value)| {
+                let other_value = lookup.get(label)?;
+                Some((label.clone(), combine(value, other_value)))
+            })
+            .unzip();
+        DataFrame::new(CategoricalRange::new(labels), data)
+    }
+}
+
+impl<I, T> DataFrame<SparseNumericIndex<I>, Vec<T>>
+where
+    I: Copy + Ord + 'static + Sync,
+{
+    /// Combine two sparse frames over the union of their keys.
+    ///
+    /// For every key, `combine` receives the value of each frame at that key, or `None` if
+    /// the frame lacks it, so the caller decides how to handle missing sides. The keys are
+    /// merged in a single pass over both sorted indices.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let a = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![1_i64, 2])), vec![1, 2]);
+    /// let b = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![2_i64, 3])), vec![20, 30]);
+    /// let sums = a.outer_join(&b, |l, r| l.unwrap_or(&0) + r.unwrap_or(&0));
+    /// assert_eq!(sums.index().indices.to_vec(), vec![1, 2, 3]);
+    /// assert_eq!(sums.data(), &vec![1, 22, 30]);
+    /// ```
+    pub fn outer_join<U, V, F>(
+        &self,
+        other: &DataFrame<SparseNumericIndex<I>, Vec<U>>,
+        combine: F,
+    ) -> DataFrame<SparseNumericIndex<I>, Vec<V>>
+    where
+        F: Fn(Option<&T>, Option<&U>) -> V,
+    {
+        let (left, right) = (&self.index.indices, &other.index.indices);
+        let (mut i, mut j) = (0, 0);
+        let mut keys = Vec::with_capacity(left.len().max(right.len()));
+        let mut data = Vec::with_capacity(left.len().max(right.len()));
+        while i < left.len() || j < right.len() {
+            let order = match (left.get(i), right.get(j)) {
+                (Some(l), Some(r)) => l.cmp(r),
+                (Some(_), None) => Ordering::Less,
+                _ => Ordering::Greater,
+            };
+            match order {
+                Ordering::Less => {
+                    keys.push(left[i]);
+                    data.push(combine(Some(&self.data[i]), None));
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    keys.push(right[j]);
+                    data.push(combine(None, Some(&other.data[j])));
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    keys.push(left[i]);
+                    data.push(combine(Some(&self.data[i]), Some(&other.data[j])));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        // SAFETY: merging two strictly increasing key sequences keeps the keys strictly
+        // increasing.
+        let indices = unsafe { SortedSet::from_sorted(keys) };
+        DataFrame::new(SparseNumericIndex::new(indices), data)
+    }
+
+    /// Reindex both frames onto the union of their keys, with `None` where a frame lacks a
+    /// key, so that elementwise operations line up by key rather than by position.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let a = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![1_i64, 3])), vec!['a', 'c']);
+    /// let b = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![2_i64])), vec![2.0]);
+    /// let (a, b) = a.align(&b);
+    /// assert_eq!(a.data(), &vec![Some('a'), None, Some('c')]);
+    /// assert_eq!(b.data(), &vec![None, Some(2.0), None]);
+    /// ```
+    pub fn align<U>(&self, other: &DataFrame<SparseNumericIndex<I>, Vec<U>>) -> Aligned<I, T, U>
+    where
+        T: Clone,
+        U: Clone,
+    {
+        let joined = self.outer_join(other, |l, r| (l.cloned(), r.cloned()));
+        let (left, right) = joined.data.into_iter().unzip();
+        (
+            DataFrame::new(joined.index.clone(), left),
+            DataFrame::new(joined.index, right),
+        )
+    }
+}
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange + Clone,
+{
+    /// Combine every row of `self` with every row of `other`, e.g. to build a pairwise
+    /// distance matrix.
+    ///
+    /// The result is indexed by both input indices, with `self`'s as the outer dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let a = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// let b = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![10, 20]);
+    /// let products = a.pairwise_map(&b, |x, y| x * y);
+    /// assert_eq!(products.data(), &vec![10, 20, 20, 40]);
+    /// ```
+    pub fn pairwise_map<J, U, V, F>(
+        &self,
+        other: &DataFrame<J, Vec<U>>,
+        mut f: F,
+    ) -> PairwiseFrame<I, J, V>
+    where
+        J: VariableRange + Clone,
+        F: FnMut(&T, &U) -> V,
+    {
+        let data = self
+            .data
+            .iter()
+            .flat_map(|a| other.data.iter().map(move |b| (a, b)))
+            .map(|(a, b)| f(a, b))
+            .collect();
+        let index = CompoundIndex::new(hlist![self.index.clone(), other.index.clone()]);
+        DataFrame::new(index, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inner_join_categorical() {
+        let left = DataFrame::new(
+            CategoricalRange::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+            vec![1, 2, 3],
+        );
+        let right = DataFrame::new(
+            CategoricalRange::new(vec!["c".to_string(), "b".to_string(), "d".to_string()]),
+            vec![30.0, 20.0, 40.0],
+        );
+
+        let joined = left.inner_join_categorical(&right, |l, r| (*l, *r));
+
+        assert_eq!(
+            joined.index(),
+            &CategoricalRange::new(vec!["b".to_string(), "c".to_string()])
+        );
+        assert_eq!(joined.data(), &vec![(2, 20.0), (3, 30.0)]);
+    }
+
+    #[test]
+    fn test_inner_join_categorical_no_overlap() {
+        let left = DataFrame::new(CategoricalRange::new(vec!["a"]), vec![1]);
+        let right = DataFrame::new(CategoricalRange::new(vec!["b"]), vec![2]);
+
+        let joined = left.inner_join_categorical(&right, |l, r| l + r);
+        assert_eq!(joined.n_rows(), 0);
+    }
+
+    #[test]
+    fn test_pairwise_map() {
+        use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+        let a = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+        let b = DataFrame::new(CategoricalRange::new(vec!["p", "q"]), vec![5, 6]);
+
+        let diffs = a.pairwise_map(&b, |x: &i32, y: &i32| (x - y).abs());
+
+        assert_eq!(diffs.index().indices.head, NumericRangeIndex::new(0, 2));
+        assert_eq!(
+            diffs.index().indices.tail.head,
+            CategoricalRange::new(vec!["p", "q"])
+        );
+        assert_eq!(diffs.data(), &vec![4, 5, 3, 4]);
+    }
+
+    #[test]
+    fn test_outer_join() {
+        let left = DataFrame::new(SparseNumericIndex::new(vec![1, 3].into()), vec!['a', 'c']);
+        let right = DataFrame::new(SparseNumericIndex::new(vec![2, 3].into()), vec![20, 30]);
+
+        let joined = left.outer_join(&right, |l, r| (l.copied(), r.copied()));
+
+        assert_eq!(joined.index().indices.to_vec(), vec![1, 2, 3]);
+        assert_eq!(
+            joined.data(),
+            &vec![(Some('a'), None), (None, Some(20)), (Some('c'), Some(30))]
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        let a = DataFrame::new(SparseNumericIndex::new(vec![1_i64, 3].into()), vec![10, 30]);
+        let b = DataFrame::new(
+            SparseNumericIndex::new(vec![2_i64, 3].into()),
+            vec!["b", "c"],
+        );
+
+        let (a, b) = a.align(&b);
+
+        let union = SparseNumericIndex::new(vec![1, 2, 3].into());
+        assert_eq!(a.index(), &union);
+        assert_eq!(b.index(), &union);
+        assert_eq!(a.data(), &vec![Some(10), None, Some(30)]);
+        assert_eq!(b.data(), &vec![None, Some("b"), Some("c")]);
+    }
+}
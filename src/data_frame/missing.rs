@@ -0,0 +1,166 @@
+//! Handling of missing values, marked in the data by a sentinel value.
+use super::core::DataFrame;
+use crate::mapped_index::VariableRange;
+use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+    T: PartialEq + Clone,
+{
+    /// Replace every `sentinel` with the closest preceding non-sentinel value.
+    ///
+    /// Leading sentinels have nothing to fill from and are left in place.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![-1, 2, -1, 4]);
+    /// df.ffill(&-1);
+    /// assert_eq!(df.data(), &vec![-1, 2, 2, 4]);
+    /// ```
+    pub fn ffill(&mut self, sentinel: &T) {
+        let mut last: Option<T> = None;
+        for value in self.data.iter_mut() {
+            if value == sentinel {
+                if let Some(last) = &last {
+                    *value = last.clone();
+                }
+            } else {
+                last = Some(value.clone());
+            }
+        }
+    }
+
+    /// Replace every `sentinel` with the closest following non-sentinel value.
+    ///
+    /// Trailing sentinels have nothing to fill from and are left in place.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, -1, 3, -1]);
+    /// df.bfill(&-1);
+    /// assert_eq!(df.data(), &vec![1, 3, 3, -1]);
+    /// ```
+    pub fn bfill(&mut self, sentinel: &T) {
+        let mut next: Option<T> = None;
+        for value in self.data.iter_mut().rev() {
+            if value == sentinel {
+                if let Some(next) = &next {
+                    *value = next.clone();
+                }
+            } else {
+                next = Some(value.clone());
+            }
+        }
+    }
+
+    /// Remove the rows equal to `sentinel`.
+    ///
+    /// The result is indexed by the original positions of the surviving rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 0, 3]);
+    /// let dropped = df.dropna(&0);
+    /// assert_eq!(dropped.index().indices.to_vec(), vec![0, 2]);
+    /// assert_eq!(dropped.data(), &vec![1, 3]);
+    /// ```
+    pub fn dropna(&self, sentinel: &T) -> DataFrame<SparseNumericIndex<i64>, Vec<T>> {
+        let (positions, data): (Vec<i64>, Vec<T>) = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| *value != sentinel)
+            .map(|(position, value)| (position as i64, value.clone()))
+            .unzip();
+        let index = SparseNumericIndex::new(positions.into());
+        DataFrame::new(index, data)
+    }
+}
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+{
+    /// The number of values satisfying `pred`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1.0, f64::NAN, 3.0]);
+    /// assert_eq!(df.count_matching(|v| v.is_nan()), 1);
+    /// ```
+    pub fn count_matching<F: Fn(&T) -> bool>(&self, pred: F) -> usize {
+        self.data.iter().filter(|v| pred(v)).count()
+    }
+
+    /// The number of values equal to `sentinel`, e.g. to report how much data is missing.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![-1, 2, -1]);
+    /// assert_eq!(df.count_eq(&-1), 2);
+    /// ```
+    pub fn count_eq(&self, sentinel: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.count_matching(|v| v == sentinel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+    const S: i32 = i32::MIN;
+
+    #[test]
+    fn test_ffill() {
+        let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, S, S, 4]);
+        df.ffill(&S);
+        assert_eq!(df.data(), &vec![1, 1, 1, 4]);
+
+        let mut leading = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![S, 2, S]);
+        leading.ffill(&S);
+        assert_eq!(leading.data(), &vec![S, 2, 2]);
+    }
+
+    #[test]
+    fn test_bfill() {
+        let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, S, S, 4]);
+        df.bfill(&S);
+        assert_eq!(df.data(), &vec![1, 4, 4, 4]);
+
+        let mut trailing = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![S, 2, S]);
+        trailing.bfill(&S);
+        assert_eq!(trailing.data(), &vec![2, 2, S]);
+    }
+
+    #[test]
+    fn test_dropna() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 5), vec![1, -1, 3, -1, 5]);
+
+        let dropped = df.dropna(&-1);
+
+        assert_eq!(
+            dropped.index(),
+            &SparseNumericIndex::new(vec![0, 2, 4].into())
+        );
+        assert_eq!(dropped.data(), &vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_count() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 5), vec![0, 1, 0, 2, 0]);
+
+        assert_eq!(df.count_eq(&0), 3);
+        assert_eq!(df.count_eq(&3), 0);
+        assert_eq!(df.count_matching(|v| *v > 0), 2);
+    }
+}
@@ -1,6 +1,20 @@
 //! DataFrame module root. See each submodule for details.
 pub mod aggregate;
+pub mod along_dim;
 pub mod core;
+pub mod dynamic;
+pub mod fill;
+pub mod join;
+pub mod missing;
+#[cfg(feature = "serde")]
+pub mod records;
+pub mod resample;
+pub mod reshape;
+pub mod rows;
+pub mod sparse;
 pub mod stack;
+pub mod stats;
 pub mod strided_index_view;
+pub mod two_dim;
 pub mod util;
+pub mod window;
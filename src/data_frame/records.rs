@@ -0,0 +1,63 @@
+//! Row-by-row serialization of DataFrames, as opposed to serializing the whole struct.
+use super::core::DataFrame;
+use crate::mapped_index::VariableRange;
+
+/// A single row of a DataFrame: its index label and its value.
+///
+/// Serializes as `{"index": ..., "value": ...}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Record<L, V> {
+    /// The index label of the row.
+    pub index: L,
+    /// The value of the row.
+    pub value: V,
+}
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+{
+    /// Collect the rows as [`Record`]s, ready to be serialized one by one, e.g. for a JSON API.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec!["a", "b"]);
+    /// let records = df.to_records();
+    /// assert_eq!(records[1].index, 1);
+    /// assert_eq!(records[1].value, &"b");
+    /// ```
+    pub fn to_records(&self) -> Vec<Record<I::Value<'_>, &T>> {
+        self.iter()
+            .map(|(index, value)| Record { index, value })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::categorical_index::CategoricalRange;
+
+    #[test]
+    fn test_to_records() {
+        let df = DataFrame::new(
+            CategoricalRange::new(vec!["x".to_string(), "y".to_string()]),
+            vec![1.5, 2.5],
+        );
+
+        let records = df.to_records();
+        let json: Vec<serde_json::Value> = records
+            .iter()
+            .map(|r| serde_json::to_value(r).unwrap())
+            .collect();
+
+        assert_eq!(
+            json,
+            vec![
+                serde_json::json!({"index": "x", "value": 1.5}),
+                serde_json::json!({"index": "y", "value": 2.5}),
+            ]
+        );
+    }
+}
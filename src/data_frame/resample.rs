@@ -0,0 +1,125 @@
+//! Changing the sampling rate of DataFrames.
+use super::core::DataFrame;
+use crate::mapped_index::numeric_range::{NumericRangeIndex, NumericRangeValue};
+use crate::mapped_index::stepped_range::SteppedRangeIndex;
+
+impl<I, T> DataFrame<NumericRangeIndex<I>, Vec<T>>
+where
+    I: NumericRangeValue,
+{
+    /// Take every `step`-th row, starting with the first.
+    ///
+    /// Unlike binned aggregation this picks rows rather than reducing them; the index
+    /// keeps the original keys of the picked rows.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0 or the index is descending.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::VariableRange;
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(5, 9), vec![1, 2, 3, 4]);
+    /// let strided = df.stride_rows(3);
+    /// assert_eq!(strided.index().iter().collect::<Vec<_>>(), vec![5, 8]);
+    /// assert_eq!(strided.data(), &vec![1, 4]);
+    /// ```
+    pub fn stride_rows(&self, step: usize) -> DataFrame<SteppedRangeIndex<I>, Vec<T>>
+    where
+        T: Clone,
+    {
+        assert!(step > 0, "step must be positive");
+        assert!(
+            !self.index.is_descending(),
+            "stride_rows requires an ascending index"
+        );
+        let data: Vec<T> = self.data.iter().step_by(step).cloned().collect();
+        let index = SteppedRangeIndex::new(self.index.start, step, data.len());
+        DataFrame::new(index, data)
+    }
+}
+
+impl<I> DataFrame<NumericRangeIndex<I>, Vec<f64>>
+where
+    I: NumericRangeValue,
+{
+    /// Downsample by averaging each consecutive group of `factor` rows into one row.
+    ///
+    /// The final group averages whatever rows remain. Each group is labelled with the key of
+    /// its first row, so the result is indexed by a [`SteppedRangeIndex`] whose step is
+    /// `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is 0 or the index is descending.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::stepped_range::SteppedRangeIndex;
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(10, 13), vec![1.0, 2.0, 6.0]);
+    /// let resampled = df.resample_mean(2);
+    /// assert_eq!(resampled.index(), &SteppedRangeIndex::new(10, 2, 2));
+    /// assert_eq!(resampled.data(), &vec![1.5, 6.0]);
+    /// ```
+    pub fn resample_mean(&self, factor: usize) -> DataFrame<SteppedRangeIndex<I>, Vec<f64>> {
+        assert!(factor > 0, "factor must be positive");
+        assert!(
+            !self.index.is_descending(),
+            "resample_mean requires an ascending index"
+        );
+        let data: Vec<f64> = self
+            .data
+            .chunks(factor)
+            .map(|group| group.iter().sum::<f64>() / group.len() as f64)
+            .collect();
+        let index = SteppedRangeIndex::new(self.index.start, factor, data.len());
+        DataFrame::new(index, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::VariableRange;
+
+    #[test]
+    fn test_stride_rows() {
+        let df = DataFrame::new(
+            NumericRangeIndex::<usize>::new(0, 5),
+            vec![10, 20, 30, 40, 50],
+        );
+
+        let strided = df.stride_rows(2);
+
+        assert_eq!(strided.index(), &SteppedRangeIndex::new(0, 2, 3));
+        assert_eq!(strided.index().iter().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(strided.data(), &vec![10, 30, 50]);
+
+        let pairs: Vec<(usize, i32)> = strided.iter().map(|(i, v)| (i, *v)).collect();
+        assert_eq!(pairs[2], (4, 50));
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be positive")]
+    fn test_stride_rows_zero_step() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 1), vec![1]);
+        let _ = df.stride_rows(0);
+    }
+
+    #[test]
+    fn test_resample_mean() {
+        let df = DataFrame::new(
+            NumericRangeIndex::<usize>::new(0, 6),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+
+        let resampled = df.resample_mean(2);
+        assert_eq!(resampled.index(), &SteppedRangeIndex::new(0, 2, 3));
+        assert_eq!(resampled.index().iter().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(resampled.data(), &vec![1.5, 3.5, 5.5]);
+
+        // The final partial group averages its available rows
+        let resampled = df.resample_mean(4);
+        assert_eq!(resampled.data(), &vec![2.5, 5.5]);
+    }
+}
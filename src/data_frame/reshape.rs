@@ -0,0 +1,321 @@
+//! Reshaping DataFrames between long (one record per row) and wide (grid) layouts.
+use super::core::DataFrame;
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist, IndexShape};
+use crate::mapped_index::numeric_range::NumericRangeIndex;
+use crate::mapped_index::singleton_index::SingletonRange;
+use crate::mapped_index::{FromSize, VariableRange};
+use frunk::traits::IntoReverse;
+use frunk::{HList, hlist};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A grid of optional values with categorical row and column labels, as built by
+/// [`DataFrame::pivot`].
+pub type PivotTable<RK, CK, V> =
+    DataFrame<CompoundIndex<HList![CategoricalRange<RK>, CategoricalRange<CK>]>, Vec<Option<V>>>;
+
+/// Long-format `(row_label, col_label, value)` records, as produced by [`DataFrame::melt`].
+pub type MeltedRecords<R, C, T> = DataFrame<NumericRangeIndex<usize>, Vec<(R, C, T)>>;
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+{
+    /// Arrange the records of this frame into a grid, spreadsheet pivot-table style.
+    ///
+    /// Each record is placed at the row given by `row_key` and the column given by
+    /// `col_key`. Rows and columns are labelled by the distinct keys in order of first
+    /// appearance. Combinations that no record maps to are `None`; if several records map to
+    /// the same cell, the last one wins.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    ///
+    /// let records = vec![("2024", "apples", 3), ("2024", "pears", 5), ("2025", "apples", 4)];
+    /// let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 3), records);
+    /// let grid = df.pivot(|r| r.0, |r| r.1, |r| r.2);
+    /// assert_eq!(grid.data(), &vec![Some(3), Some(5), Some(4), None]);
+    /// ```
+    pub fn pivot<RK, CK, V, FR, FC, FV>(
+        &self,
+        mut row_key: FR,
+        mut col_key: FC,
+        mut value: FV,
+    ) -> PivotTable<RK, CK, V>
+    where
+        RK: Eq + Hash + Clone + Sync,
+        CK: Eq + Hash + Clone + Sync,
+        FR: FnMut(&T) -> RK,
+        FC: FnMut(&T) -> CK,
+        FV: FnMut(&T) -> V,
+    {
+        let mut row_labels = Vec::new();
+        let mut row_positions = HashMap::new();
+        let mut col_labels = Vec::new();
+        let mut col_positions = HashMap::new();
+
+        let cells: Vec<(usize, usize, V)> = self
+            .data
+            .iter()
+            .map(|record| {
+                let row = *row_positions
+                    .entry(row_key(record))
+                    .or_insert_with_key(|k| {
+                        row_labels.push(k.clone());
+                        row_labels.len() - 1
+                    });
+                let col = *col_positions
+                    .entry(col_key(record))
+                    .or_insert_with_key(|k| {
+                        col_labels.push(k.clone());
+                        col_labels.len() - 1
+                    });
+                (row, col, value(record))
+            })
+            .collect();
+
+        let n_cols = col_labels.len();
+        let mut data: Vec<Option<V>> = (0..row_labels.len() * n_cols).map(|_| None).collect();
+        for (row, col, v) in cells {
+            data[row * n_cols + col] = Some(v);
+        }
+
+        let index = CompoundIndex {
+            indices: hlist![
+                CategoricalRange::new(row_labels),
+                CategoricalRange::new(col_labels)
+            ],
+        };
+        DataFrame::new(index, data)
+    }
+}
+
+impl<A, B, T> DataFrame<CompoundIndex<HList![A, B]>, Vec<T>>
+where
+    A: VariableRange,
+    B: VariableRange,
+{
+    /// Flatten the grid into long-format `(row_label, col_label, value)` records, the
+    /// inverse of [`DataFrame::pivot`].
+    ///
+    /// Records are produced in row-major order and indexed `0..n_rows * n_cols`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     NumericRangeIndex::<i32>::new(5, 7),
+    /// ]);
+    /// let df = DataFrame::new(index, vec!['a', 'b']);
+    /// assert_eq!(df.melt().data(), &vec![(0, 5, 'a'), (0, 6, 'b')]);
+    /// ```
+    pub fn melt(&self) -> MeltedRecords<A::Value<'_>, B::Value<'_>, T>
+    where
+        T: Clone,
+    {
+        let data: Vec<_> = self
+            .index
+            .iter()
+            .zip(&self.data)
+            .map(|(labels, value)| {
+                let (row, col) = labels.into_tuple2();
+                (row, col, value.clone())
+            })
+            .collect();
+        DataFrame::new(NumericRangeIndex::from_size(data.len()), data)
+    }
+}
+
+impl<K, T> DataFrame<SingletonRange<K>, Vec<T>>
+where
+    K: Sync + Clone,
+{
+    /// Broadcast the single value of this frame over `index`, repeating it once per index
+    /// value.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::singleton_index::SingletonRange;
+    /// let scalar = DataFrame::new(SingletonRange::new("total"), vec![1.5]);
+    /// let broadcast = scalar.broadcast_to(NumericRangeIndex::<i32>::new(0, 2));
+    /// assert_eq!(broadcast.data(), &vec![1.5, 1.5]);
+    /// ```
+    pub fn broadcast_to<I: VariableRange>(&self, index: I) -> DataFrame<I, Vec<T>>
+    where
+        T: Clone,
+    {
+        let data = vec![self.data[0].clone(); index.size()];
+        DataFrame::new(index, data)
+    }
+}
+
+impl<Indices, T> DataFrame<CompoundIndex<Indices>, Vec<T>>
+where
+    Indices: IndexHlist + IndexShape + IntoReverse,
+    Indices::Output: IndexHlist,
+{
+    /// Reverse the order of all dimensions, so that the first dimension becomes the last
+    /// and vice versa. For two dimensions this is the ordinary matrix transpose.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 3)
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(df.transpose_all().data(), &vec![1, 4, 2, 5, 3, 6]);
+    /// ```
+    pub fn transpose_all(self) -> DataFrame<CompoundIndex<Indices::Output>, Vec<T>>
+    where
+        T: Clone,
+    {
+        let shape = self.index.indices.shape();
+        let mut strides = vec![1; shape.len()];
+        for dim in (1..shape.len()).rev() {
+            strides[dim - 1] = strides[dim] * shape[dim];
+        }
+
+        // The reversed frame's last dimension is our first, so peeling coordinates off the
+        // flat position from the back visits our dimensions front to back.
+        let data = (0..self.data.len())
+            .map(|flat| {
+                let mut rest = flat;
+                let source = shape
+                    .iter()
+                    .zip(&strides)
+                    .fold(0, |source, (size, stride)| {
+                        let coord = rest % size;
+                        rest /= size;
+                        source + coord * stride
+                    });
+                self.data[source].clone()
+            })
+            .collect();
+        DataFrame::new(CompoundIndex::new(self.index.indices.into_reverse()), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pivot() {
+        let records = vec![
+            ("north", "q1", 10),
+            ("south", "q2", 40),
+            ("north", "q2", 20),
+            ("south", "q1", 30),
+        ];
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 4), records);
+
+        let grid = df.pivot(|r| r.0, |r| r.1, |r| r.2);
+
+        assert_eq!(
+            grid.index().indices.head,
+            CategoricalRange::new(vec!["north", "south"])
+        );
+        assert_eq!(
+            grid.index().indices.tail.head,
+            CategoricalRange::new(vec!["q1", "q2"])
+        );
+        assert_eq!(grid.data(), &vec![Some(10), Some(20), Some(30), Some(40)]);
+    }
+
+    #[test]
+    fn test_pivot_missing_combination() {
+        let records = vec![("a", 'x', 1.0), ("b", 'y', 2.0)];
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), records);
+
+        let grid = df.pivot(|r| r.0, |r| r.1, |r| r.2);
+
+        assert_eq!(grid.data(), &vec![Some(1.0), None, None, Some(2.0)]);
+    }
+
+    #[test]
+    fn test_melt() {
+        let index = CompoundIndex::new(hlist![
+            CategoricalRange::new(vec!["north", "south"]),
+            CategoricalRange::new(vec!["q1", "q2"])
+        ]);
+        let df = DataFrame::new(index, vec![10, 20, 30, 40]);
+
+        let records = df.melt();
+
+        assert_eq!(records.index(), &NumericRangeIndex::new(0, 4));
+        assert_eq!(
+            records.data(),
+            &vec![
+                (&"north", &"q1", 10),
+                (&"north", &"q2", 20),
+                (&"south", &"q1", 30),
+                (&"south", &"q2", 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_melt_inverts_pivot() {
+        let records = vec![("a", 'x', 1), ("a", 'y', 2), ("b", 'x', 3), ("b", 'y', 4)];
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 4), records.clone());
+
+        let grid = df.pivot(|r| r.0, |r| r.1, |r| r.2);
+        let melted = grid.melt();
+
+        let round_trip: Vec<_> = melted
+            .data()
+            .iter()
+            .map(|(row, col, v)| (**row, **col, v.unwrap()))
+            .collect();
+        assert_eq!(round_trip, records);
+    }
+
+    #[test]
+    fn test_broadcast_to() {
+        let scalar = DataFrame::new(SingletonRange::new(()), vec![42]);
+
+        let broadcast = scalar.broadcast_to(NumericRangeIndex::<i32>::new(0, 3));
+
+        assert_eq!(broadcast.index(), &NumericRangeIndex::new(0, 3));
+        assert_eq!(broadcast.data(), &vec![42, 42, 42]);
+    }
+
+    #[test]
+    fn test_transpose_all() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<usize>::new(0, 2),
+            CategoricalRange::new(vec!['a', 'b', 'c']),
+            NumericRangeIndex::<i32>::new(10, 14)
+        ]);
+        let df = DataFrame::new(index, (0..24).collect::<Vec<_>>());
+
+        let transposed = df.transpose_all();
+
+        assert_eq!(transposed.index().indices.shape(), vec![4, 3, 2]);
+        assert_eq!(
+            transposed.index().indices.head,
+            NumericRangeIndex::new(10, 14)
+        );
+        // Element (i, j, k) of the original is at (k, j, i) in the transposed frame.
+        for (i, j, k) in [(0, 0, 0), (1, 2, 3), (0, 1, 2), (1, 0, 3)] {
+            let original = (i * 3 + j) * 4 + k;
+            let flat = (k * 3 + j) * 2 + i;
+            assert_eq!(transposed.data()[flat], original);
+        }
+        let (k, (j, i)) = transposed.index().unflatten_index_value(7).into_tuple2();
+        assert_eq!((i, *j, k), (1, 'a', 11));
+        assert_eq!(transposed.data()[7], 13);
+    }
+}
@@ -0,0 +1,397 @@
+//! Row-level reordering and restructuring of DataFrames.
+use super::core::{DataFrame, FrameData};
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::numeric_range::{NumericRangeIndex, NumericRangeValue};
+use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+use crate::mapped_index::{FromSize, VariableRange};
+use std::ops::Add;
+
+/// The rows that satisfied a predicate and the rows that did not, each indexed by their
+/// original positions, as returned by [`DataFrame::partition`].
+pub type Partitioned<T> = (
+    DataFrame<SparseNumericIndex<i64>, Vec<T>>,
+    DataFrame<SparseNumericIndex<i64>, Vec<T>>,
+);
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+{
+    /// Reorder the rows so that row `k` of the result is row `perm[k]` of `self`.
+    ///
+    /// The result is indexed by the original row positions, in their new order. A sparse
+    /// index cannot be used for this since it must stay sorted.
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation of `0..n_rows`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec!['a', 'b', 'c']);
+    /// let permuted = df.permute(&[1, 2, 0]);
+    /// assert_eq!(permuted.index().values, vec![1, 2, 0]);
+    /// assert_eq!(permuted.data(), &vec!['b', 'c', 'a']);
+    /// ```
+    pub fn permute(&self, perm: &[usize]) -> DataFrame<CategoricalRange<usize>, Vec<T>>
+    where
+        T: Clone,
+    {
+        assert_eq!(
+            perm.len(),
+            self.n_rows(),
+            "Permutation length must match the number of rows"
+        );
+        let mut seen = vec![false; perm.len()];
+        for &p in perm {
+            assert!(
+                p < seen.len() && !std::mem::replace(&mut seen[p], true),
+                "Invalid permutation: {:?}",
+                perm
+            );
+        }
+
+        let data = perm.iter().map(|&p| self.data[p].clone()).collect();
+        DataFrame::new(CategoricalRange::new(perm.to_vec()), data)
+    }
+
+    /// Split the rows into those whose value satisfies `f` and those whose value does not,
+    /// in a single pass.
+    ///
+    /// Both frames are indexed by the original positions of their rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1, 2, 3]);
+    /// let (odd, even) = df.partition(|v| v % 2 == 1);
+    /// assert_eq!(odd.index().indices.to_vec(), vec![0, 2]);
+    /// assert_eq!(even.data(), &vec![2]);
+    /// ```
+    pub fn partition<F: Fn(&T) -> bool>(&self, f: F) -> Partitioned<T>
+    where
+        T: Clone,
+    {
+        let (mut matching, mut rest) = ((Vec::new(), Vec::new()), (Vec::new(), Vec::new()));
+        for (position, value) in self.data.iter().enumerate() {
+            let (positions, data) = if f(value) { &mut matching } else { &mut rest };
+            positions.push(position as i64);
+            data.push(value.clone());
+        }
+        let into_frame = |(positions, data): (Vec<i64>, Vec<T>)| {
+            DataFrame::new(SparseNumericIndex::new(positions.into()), data)
+        };
+        (into_frame(matching), into_frame(rest))
+    }
+
+    /// The longest prefix of rows whose values all satisfy `f`, with a fresh `0..len`
+    /// index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, 2, 3, 4]);
+    /// assert_eq!(df.take_while(|v| *v < 3).data(), &vec![1, 2]);
+    /// ```
+    pub fn take_while<F: FnMut(&T) -> bool>(
+        &self,
+        mut f: F,
+    ) -> DataFrame<NumericRangeIndex<usize>, Vec<T>>
+    where
+        T: Clone,
+    {
+        let data: Vec<T> = self.data.iter().take_while(|v| f(v)).cloned().collect();
+        DataFrame::new(NumericRangeIndex::from_size(data.len()), data)
+    }
+
+    /// The rows from the first one whose value does not satisfy `f` on, with a fresh
+    /// `0..len` index. This is the complement of [`DataFrame::take_while`].
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, 2, 3, 4]);
+    /// assert_eq!(df.skip_while(|v| *v < 3).data(), &vec![3, 4]);
+    /// ```
+    pub fn skip_while<F: FnMut(&T) -> bool>(
+        &self,
+        mut f: F,
+    ) -> DataFrame<NumericRangeIndex<usize>, Vec<T>>
+    where
+        T: Clone,
+    {
+        let data: Vec<T> = self.data.iter().skip_while(|v| f(v)).cloned().collect();
+        DataFrame::new(NumericRangeIndex::from_size(data.len()), data)
+    }
+}
+
+impl<T> DataFrame<NumericRangeIndex<usize>, Vec<T>> {
+    /// Split the frame into the rows before `mid` and the rows from `mid` on, each with a
+    /// fresh `0..len` index.
+    ///
+    /// # Panics
+    /// Panics if `mid > n_rows`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 3), vec![1, 2, 3]);
+    /// let (head, tail) = df.split_at(1);
+    /// assert_eq!(head.data(), &vec![1]);
+    /// assert_eq!(tail.data(), &vec![2, 3]);
+    /// assert_eq!(tail.index().start, 0);
+    /// ```
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        assert!(
+            mid <= self.n_rows(),
+            "Split point {} out of bounds (n_rows: {})",
+            mid,
+            self.n_rows()
+        );
+        let mut head = self.data;
+        let tail = head.split_off(mid);
+        (
+            DataFrame::new(NumericRangeIndex::from_size(head.len()), head),
+            DataFrame::new(NumericRangeIndex::from_size(tail.len()), tail),
+        )
+    }
+
+    /// Alternate the rows of `self` and `other`, producing `[a0, b0, a1, b1, ...]` with a
+    /// fresh `0..len` index.
+    ///
+    /// # Panics
+    /// Panics if the frames have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let a = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec!['a', 'c']);
+    /// let b = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec!['b', 'd']);
+    /// assert_eq!(a.interleave(&b).data(), &vec!['a', 'b', 'c', 'd']);
+    /// ```
+    pub fn interleave(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        assert_eq!(
+            self.n_rows(),
+            other.n_rows(),
+            "Interleaved frames must have the same length"
+        );
+        let data: Vec<T> = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .flat_map(|(a, b)| [a.clone(), b.clone()])
+            .collect();
+        DataFrame::new(NumericRangeIndex::from_size(data.len()), data)
+    }
+}
+
+impl<I, D> DataFrame<NumericRangeIndex<I>, D>
+where
+    I: NumericRangeValue + Add<Output = I>,
+    D: FrameData,
+{
+    /// Shift the index labels by `delta` in place, leaving the data untouched.
+    ///
+    /// Useful for relabelling the pieces produced by [`DataFrame::split_at`].
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let mut df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]);
+    /// df.offset_index(-5);
+    /// assert_eq!(df.index(), &NumericRangeIndex::new(-5, -3));
+    /// ```
+    pub fn offset_index(&mut self, delta: I) {
+        self.index.start = self.index.start + delta;
+        self.index.end = self.index.end + delta;
+    }
+}
+
+impl<I, T> DataFrame<NumericRangeIndex<I>, Vec<T>>
+where
+    I: NumericRangeValue,
+{
+    /// Concatenate frames whose ranges follow on from each other, e.g. `0..2`, `2..4` and
+    /// `4..5` into `0..5`.
+    ///
+    /// Returns `None` if there are no frames, or if any frame does not start where the
+    /// previous one ended.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let a = DataFrame::new(NumericRangeIndex::<i32>::new(0, 1), vec![1]);
+    /// let b = DataFrame::new(NumericRangeIndex::<i32>::new(1, 3), vec![2, 3]);
+    /// let joined = DataFrame::concat_many([a, b]).unwrap();
+    /// assert_eq!(joined.index(), &NumericRangeIndex::new(0, 3));
+    /// assert_eq!(joined.data(), &vec![1, 2, 3]);
+    /// ```
+    pub fn concat_many(frames: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut frames = frames.into_iter();
+        let first = frames.next()?;
+        let start = first.index.start;
+        let mut end = first.index.end;
+        let mut data = first.data;
+        for frame in frames {
+            if frame.index.start != end {
+                return None;
+            }
+            end = frame.index.end;
+            data.extend(frame.data);
+        }
+
+        let index = NumericRangeIndex { start, end };
+        // Catches frames that are adjacent but run in opposite directions.
+        (index.size() == data.len()).then(|| DataFrame::new(index, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permute() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+
+        let permuted = df.permute(&[2, 0, 1]);
+
+        assert_eq!(permuted.index(), &CategoricalRange::new(vec![2, 0, 1]));
+        assert_eq!(permuted.data(), &vec![30, 10, 20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid permutation")]
+    fn test_permute_repeated_position() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![10, 20, 30]);
+        let _ = df.permute(&[0, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid permutation")]
+    fn test_permute_out_of_range() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![10, 20]);
+        let _ = df.permute(&[0, 2]);
+    }
+
+    #[test]
+    fn test_partition() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, 2, 3, 4]);
+
+        let (even, odd) = df.partition(|v| v % 2 == 0);
+
+        assert_eq!(even.index(), &SparseNumericIndex::new(vec![1, 3].into()));
+        assert_eq!(even.data(), &vec![2, 4]);
+        assert_eq!(odd.index(), &SparseNumericIndex::new(vec![0, 2].into()));
+        assert_eq!(odd.data(), &vec![1, 3]);
+    }
+
+    #[test]
+    fn test_take_and_skip_while() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(5, 9), vec![1, 2, 3, 4]);
+
+        let head = df.take_while(|v| *v < 3);
+        assert_eq!(head.index(), &NumericRangeIndex::new(0, 2));
+        assert_eq!(head.data(), &vec![1, 2]);
+
+        let tail = df.skip_while(|v| *v < 3);
+        assert_eq!(tail.index(), &NumericRangeIndex::new(0, 2));
+        assert_eq!(tail.data(), &vec![3, 4]);
+
+        assert_eq!(df.take_while(|_| false).n_rows(), 0);
+        assert_eq!(df.skip_while(|_| true).n_rows(), 0);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 5), vec![1, 2, 3, 4, 5]);
+
+        let (head, tail) = df.split_at(2);
+
+        assert_eq!(head.index(), &NumericRangeIndex::new(0, 2));
+        assert_eq!(head.data(), &vec![1, 2]);
+        assert_eq!(tail.index(), &NumericRangeIndex::new(0, 3));
+        assert_eq!(tail.data(), &vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_split_at_ends() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec![1, 2]);
+        let (head, tail) = df.clone().split_at(0);
+        assert_eq!((head.n_rows(), tail.n_rows()), (0, 2));
+        let (head, tail) = df.split_at(2);
+        assert_eq!((head.n_rows(), tail.n_rows()), (2, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_split_at_out_of_bounds() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec![1, 2]);
+        let _ = df.split_at(3);
+    }
+
+    #[test]
+    fn test_interleave() {
+        let odd = DataFrame::new(NumericRangeIndex::<usize>::new(0, 3), vec![1, 3, 5]);
+        let even = DataFrame::new(NumericRangeIndex::<usize>::new(0, 3), vec![2, 4, 6]);
+
+        let interleaved = odd.interleave(&even);
+
+        assert_eq!(interleaved.index(), &NumericRangeIndex::new(0, 6));
+        assert_eq!(interleaved.data(), &vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn test_interleave_mismatched_lengths() {
+        let a = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec![1, 3]);
+        let b = DataFrame::new(NumericRangeIndex::<usize>::new(0, 1), vec![2]);
+        let _ = a.interleave(&b);
+    }
+
+    #[test]
+    fn test_offset_index() {
+        let mut df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 3), vec![1, 2, 3]);
+
+        df.offset_index(10);
+
+        assert_eq!(df.index(), &NumericRangeIndex::new(10, 13));
+        assert_eq!(df.data(), &vec![1, 2, 3]);
+        assert_eq!(df.iter().next(), Some((10, &1)));
+    }
+
+    #[test]
+    fn test_concat_many() {
+        let frames = vec![
+            DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]),
+            DataFrame::new(NumericRangeIndex::<i32>::new(2, 4), vec![3, 4]),
+            DataFrame::new(NumericRangeIndex::<i32>::new(4, 5), vec![5]),
+        ];
+
+        let joined = DataFrame::concat_many(frames).unwrap();
+
+        assert_eq!(joined.index(), &NumericRangeIndex::new(0, 5));
+        assert_eq!(joined.data(), &vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_concat_many_rejects_gaps() {
+        let gap = vec![
+            DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]),
+            DataFrame::new(NumericRangeIndex::<i32>::new(3, 4), vec![4]),
+        ];
+        assert!(DataFrame::concat_many(gap).is_none());
+
+        let reversed = vec![
+            DataFrame::new(NumericRangeIndex::<i32>::new(0, 2), vec![1, 2]),
+            DataFrame::new(NumericRangeIndex::<i32>::descending(2, 0), vec![3, 2]),
+        ];
+        assert!(DataFrame::concat_many(reversed).is_none());
+
+        let none: Vec<DataFrame<NumericRangeIndex<i32>, Vec<i32>>> = vec![];
+        assert!(DataFrame::concat_many(none).is_none());
+    }
+}
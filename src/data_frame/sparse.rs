@@ -0,0 +1,386 @@
+//! Operations on DataFrames indexed by a single sparse numeric dimension.
+use super::core::{DataFrame, FrameData};
+use super::fill::{fill_sparse, lookup_sparse};
+use super::stack::InterpolationMethod;
+use crate::mapped_index::SubRange;
+use crate::mapped_index::numeric_range::NumericRangeValue;
+use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+use crate::mapped_index::stepped_range::SteppedRangeIndex;
+use num_traits::CheckedSub;
+use sorted_vec::SortedSet;
+use std::ops::{Add, Index, Sub};
+
+/// A lazily densified view of a sparse frame on a regular grid, as produced by
+/// [`DataFrame::grid_view`].
+///
+/// Each element is looked up in the sparse frame when accessed, rather than being
+/// materialized up front as [`DataFrame::to_dense`] does. Since every filled value is
+/// either a present value or the default, elements can be handed out by reference.
+#[derive(Debug, Clone)]
+pub struct GridView<'a, I: NumericRangeValue, T> {
+    source: &'a DataFrame<SparseNumericIndex<I>, Vec<T>>,
+    start: I,
+    step: usize,
+    len: usize,
+    interpolation: InterpolationMethod,
+    default: T,
+}
+
+impl<I: NumericRangeValue + Sub<Output = I>, T> Index<usize> for GridView<'_, I, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        assert!(index < self.len, "Index out of bounds.");
+        let key = self.start.nth_next(index * self.step);
+        lookup_sparse(self.source, key, &self.default)
+            .unwrap_or_else(|context| self.interpolation.select(&context))
+    }
+}
+
+impl<I: NumericRangeValue + Sub<Output = I>, T> FrameData for GridView<'_, I, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<I, T> DataFrame<SparseNumericIndex<I>, Vec<T>>
+where
+    I: Copy + Ord + 'static + Sync,
+    T: Clone,
+{
+    /// Densify the frame onto a regular grid running from its smallest to its largest key
+    /// in increments of `step`, filling keys without data using `interpolation`.
+    ///
+    /// The grid always starts at the smallest key; the largest key is only included if it
+    /// lies on the grid. An empty frame yields an empty frame.
+    ///
+    /// # Panics
+    /// Panics if `step` is not positive.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use slice_and_dice::data_frame::stack::InterpolationMethod;
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 4]));
+    /// let df = DataFrame::new(idx, vec![10, 40]);
+    /// let dense = df.to_dense(2, InterpolationMethod::Previous, 0);
+    /// assert_eq!(dense.index().indices.to_vec(), vec![0, 2, 4]);
+    /// assert_eq!(dense.data(), &vec![10, 10, 40]);
+    /// ```
+    pub fn to_dense(
+        &self,
+        step: I,
+        interpolation: InterpolationMethod,
+        default: T,
+    ) -> DataFrame<SparseNumericIndex<I>, Vec<T>>
+    where
+        I: Add<Output = I> + Sub<Output = I> + Default,
+    {
+        assert!(step > I::default(), "step must be positive");
+
+        let mut keys = Vec::new();
+        if let (Some(&first), Some(&last)) = (self.index.indices.first(), self.index.indices.last())
+        {
+            let mut key = first;
+            keys.push(key);
+            // Compare against the remaining distance to avoid overflowing past `last`.
+            while last - key >= step {
+                key = key + step;
+                keys.push(key);
+            }
+        }
+
+        let data = fill_sparse(self, keys.iter().copied(), &interpolation, &default).collect();
+        // SAFETY: keys start at the smallest key and strictly increase by a positive step.
+        let indices = unsafe { SortedSet::from_sorted(keys) };
+        DataFrame::new(SparseNumericIndex::new(indices), data)
+    }
+
+    /// Like [`DataFrame::to_dense`], but the returned frame fills in values on access
+    /// instead of allocating the dense data. Useful for consumers that make a single pass
+    /// over the grid.
+    ///
+    /// The grid runs from the smallest key in increments of `step`, indexed by a
+    /// [`SteppedRangeIndex`]. An empty frame yields an empty grid starting at `I::default()`.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use slice_and_dice::data_frame::stack::InterpolationMethod;
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 4]));
+    /// let df = DataFrame::new(idx, vec![10, 40]);
+    /// let grid = df.grid_view(2, InterpolationMethod::Previous, 0);
+    /// let pairs: Vec<(i64, i32)> = grid.iter().map(|(k, v)| (k, *v)).collect();
+    /// assert_eq!(pairs, vec![(0, 10), (2, 10), (4, 40)]);
+    /// ```
+    pub fn grid_view(
+        &self,
+        step: usize,
+        interpolation: InterpolationMethod,
+        default: T,
+    ) -> DataFrame<SteppedRangeIndex<I>, GridView<'_, I, T>>
+    where
+        I: NumericRangeValue + Sub<Output = I> + Default,
+    {
+        assert!(step > 0, "step must be positive");
+        let (start, len) = match (self.index.indices.first(), self.index.indices.last()) {
+            (Some(&first), Some(last)) => (first, first.distance(last) / step + 1),
+            _ => (I::default(), 0),
+        };
+        let view = GridView {
+            source: self,
+            start,
+            step,
+            len,
+            interpolation,
+            default,
+        };
+        DataFrame::new(SteppedRangeIndex::new(start, step, len), view)
+    }
+
+    /// Sort the rows by key, restoring the sorted-index invariant of a frame whose index
+    /// was built out of order with [`SparseNumericIndex::from_sorted_unchecked`].
+    ///
+    /// The sort is stable, so rows with equal keys keep their relative order. Duplicate keys
+    /// are not removed; check [`SparseNumericIndex::is_strictly_increasing`] afterwards if
+    /// the input may contain them.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    ///
+    /// let idx = unsafe { SparseNumericIndex::<i64>::from_sorted_unchecked(vec![2, 0]) };
+    /// let mut df = DataFrame::new(idx, vec!['b', 'a']);
+    /// df.sort_index();
+    /// assert_eq!(df.index().indices.to_vec(), vec![0, 2]);
+    /// assert_eq!(df.data(), &vec!['a', 'b']);
+    /// ```
+    pub fn sort_index(&mut self) {
+        let keys = std::mem::take(&mut self.index.indices).into_vec();
+        let mut rows: Vec<(I, T)> = keys.into_iter().zip(self.data.drain(..)).collect();
+        rows.sort_by_key(|(key, _)| *key);
+        let (keys, data): (Vec<I>, Vec<T>) = rows.into_iter().unzip();
+        // SAFETY: the keys were just sorted.
+        self.index.indices = unsafe { SortedSet::from_sorted(keys) };
+        self.data = data;
+    }
+
+    /// Apply `f` to a key-based (rather than count-based) rolling window: for each key,
+    /// `f` receives the values of all keys in `[key - window, key]`.
+    ///
+    /// Windows reaching below the smallest representable key are clamped rather than
+    /// overflowing.
+    ///
+    /// # Panics
+    /// Panics if `window` is negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 1, 10]));
+    /// let df = DataFrame::new(idx, vec![1, 2, 3]);
+    /// let counts = df.rolling_by_key(3, |w| w.len());
+    /// assert_eq!(counts.data(), &vec![1, 2, 1]);
+    /// ```
+    pub fn rolling_by_key<R, F>(
+        &self,
+        window: I,
+        mut f: F,
+    ) -> DataFrame<SparseNumericIndex<I>, Vec<R>>
+    where
+        I: CheckedSub + Default,
+        F: FnMut(&[T]) -> R,
+    {
+        assert!(window >= I::default(), "window must be non-negative");
+        let keys = &self.index.indices;
+        let mut start = 0;
+        let data = keys
+            .iter()
+            .enumerate()
+            .map(|(end, &key)| {
+                // If `key - window` underflows, every earlier key is in the window.
+                if let Some(lowest) = key.checked_sub(&window) {
+                    // Keys are sorted, so the window start only ever moves forward.
+                    while start < end && keys[start] < lowest {
+                        start += 1;
+                    }
+                }
+                f(&self.data[start..=end])
+            })
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+
+    /// Keep only the rows whose key lies in `[lo, hi]`, inclusive on both ends.
+    ///
+    /// The bounds are located by binary search. If `lo > hi` the result is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 5, 10]));
+    /// let df = DataFrame::new(idx, vec!['a', 'b', 'c']);
+    /// let clipped = df.clip_to_range(1, 10);
+    /// assert_eq!(clipped.data(), &vec!['b', 'c']);
+    /// ```
+    pub fn clip_to_range(&self, lo: I, hi: I) -> DataFrame<SparseNumericIndex<I>, Vec<T>> {
+        let keys = &self.index.indices;
+        let start = keys.partition_point(|&k| k < lo);
+        let end = keys.partition_point(|&k| k <= hi).max(start);
+        DataFrame::new(
+            self.index.sub_range(start, end),
+            self.data[start..end].to_vec(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dense() {
+        let index = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 5, 10]));
+        let df = DataFrame::new(index, vec![1, 2, 3]);
+
+        // Already on the grid: unchanged
+        let dense = df.to_dense(5, InterpolationMethod::Nearest, 0);
+        assert_eq!(dense.index(), df.index());
+        assert_eq!(dense.data(), &vec![1, 2, 3]);
+
+        // Finer grid, filling from the previous key
+        let dense = df.to_dense(2, InterpolationMethod::Previous, 0);
+        assert_eq!(dense.index().indices.to_vec(), vec![0, 2, 4, 6, 8, 10]);
+        assert_eq!(dense.data(), &vec![1, 1, 1, 2, 2, 3]);
+
+        let dense = df.to_dense(1, InterpolationMethod::Next, 0);
+        assert_eq!(dense.data(), &vec![1, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3]);
+
+        // Keys 3, 4, 8 and 9 are closer to the next present key
+        let dense = df.to_dense(1, InterpolationMethod::Nearest, 0);
+        assert_eq!(dense.data(), &vec![1, 1, 1, 2, 2, 2, 2, 2, 3, 3, 3]);
+
+        let dense = df.to_dense(3, InterpolationMethod::Default, -1);
+        assert_eq!(dense.index().indices.to_vec(), vec![0, 3, 6, 9]);
+        assert_eq!(dense.data(), &vec![1, -1, -1, -1]);
+    }
+
+    #[test]
+    fn test_to_dense_empty() {
+        let df = DataFrame::new(
+            SparseNumericIndex::<i64>::new(SortedSet::new()),
+            Vec::<i32>::new(),
+        );
+        let dense = df.to_dense(1, InterpolationMethod::Nearest, 0);
+        assert_eq!(dense.n_rows(), 0);
+    }
+
+    #[test]
+    fn test_rolling_by_key() {
+        let index = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 2, 5, 11]));
+        let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+
+        let sums = df.rolling_by_key(5, |w| w.iter().sum::<i32>());
+
+        assert_eq!(sums.index(), df.index());
+        // 0: {0}, 2: {0, 2}, 5: {0, 2, 5}, 11: {11}
+        assert_eq!(sums.data(), &vec![1, 3, 6, 4]);
+    }
+
+    #[test]
+    fn test_rolling_by_key_extreme_keys() {
+        let index =
+            SparseNumericIndex::new(SortedSet::from(vec![-2_000_000_000_i32, 2_000_000_000]));
+        let df = DataFrame::new(index, vec![1, 2]);
+
+        let counts = df.rolling_by_key(i32::MAX, |w| w.len());
+        assert_eq!(counts.data(), &vec![1, 1]);
+
+        let counts = df.rolling_by_key(0, |w| w.len());
+        assert_eq!(counts.data(), &vec![1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "window must be non-negative")]
+    fn test_rolling_by_key_negative_window() {
+        let index = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 1]));
+        let df = DataFrame::new(index, vec![1, 2]);
+        let _ = df.rolling_by_key(-1, |w| w.len());
+    }
+
+    #[test]
+    fn test_clip_to_range() {
+        let index = SparseNumericIndex::new(SortedSet::from(vec![1, 3, 5, 7, 9]));
+        let df = DataFrame::new(index, vec![10, 30, 50, 70, 90]);
+
+        let clipped = df.clip_to_range(3, 7);
+        assert_eq!(clipped.index().indices.to_vec(), vec![3, 5, 7]);
+        assert_eq!(clipped.data(), &vec![30, 50, 70]);
+
+        // Bounds between keys
+        let clipped = df.clip_to_range(4, 100);
+        assert_eq!(clipped.index().indices.to_vec(), vec![5, 7, 9]);
+
+        assert_eq!(df.clip_to_range(7, 3).n_rows(), 0);
+        assert_eq!(df.clip_to_range(10, 20).n_rows(), 0);
+    }
+
+    #[test]
+    fn test_grid_view_matches_to_dense() {
+        let index = SparseNumericIndex::new(SortedSet::from(vec![-3_i64, 0, 5, 10]));
+        let df = DataFrame::new(index, vec![7, 1, 2, 3]);
+
+        for method in [
+            InterpolationMethod::Nearest,
+            InterpolationMethod::Previous,
+            InterpolationMethod::Next,
+            InterpolationMethod::Default,
+        ] {
+            for step in [1, 2, 4, 13] {
+                let dense = df.to_dense(step as i64, method, -1);
+                let view = df.grid_view(step, method, -1);
+
+                assert_eq!(view.n_rows(), dense.n_rows());
+                let keys: Vec<i64> = view.iter().map(|(k, _)| k).collect();
+                assert_eq!(keys, dense.index().indices.to_vec());
+                let values: Vec<i32> = (0..view.n_rows()).map(|i| view.data()[i]).collect();
+                assert_eq!(&values, dense.data());
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_view_empty() {
+        let df = DataFrame::new(
+            SparseNumericIndex::<i64>::new(SortedSet::new()),
+            Vec::<i32>::new(),
+        );
+        let view = df.grid_view(1, InterpolationMethod::Nearest, 0);
+        assert_eq!(view.n_rows(), 0);
+    }
+
+    #[test]
+    fn test_sort_index() {
+        let index = unsafe { SparseNumericIndex::<i32>::from_sorted_unchecked(vec![5, 1, 3]) };
+        let mut df = DataFrame::new(index, vec!['e', 'a', 'c']);
+        assert!(!df.index().is_strictly_increasing());
+
+        df.sort_index();
+
+        assert!(df.index().is_strictly_increasing());
+        assert_eq!(df.index().indices.to_vec(), vec![1, 3, 5]);
+        assert_eq!(df.data(), &vec!['a', 'c', 'e']);
+        assert_eq!(df.clip_to_range(2, 5).data(), &vec!['c', 'e']);
+    }
+}
@@ -1,12 +1,13 @@
 //! Stacking logic for DataFrame.
 use super::core::{DataFrame, FrameData};
+use super::fill::{FillContext, FillPolicy, fill_sparse};
 use crate::mapped_index::VariableRange;
+use crate::mapped_index::categorical_index::CategoricalRange;
 use crate::mapped_index::compound_index::CompoundIndex;
 use crate::mapped_index::numeric_range::NumericRangeIndex;
 use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
 use frunk::{HList, hlist};
 use sorted_vec::SortedSet;
-use std::cmp::Ordering;
 
 /// Interpolation method for missing data points.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +31,83 @@ pub enum ExtrapolationMethod {
     Default,
 }
 
+/// The fill policy behind [`DataFrame::stack_sparse`].
+///
+/// Between two present keys, `Nearest` keeps the previous value, as `stack_sparse` always
+/// has, so no distance between keys is needed. Use [`DataFrame::stack_sparse_with`] with
+/// [`NearestFill`](super::fill::NearestFill) to pick the closer neighbour instead.
+struct StackSparseFill {
+    interpolation: InterpolationMethod,
+    extrapolation: ExtrapolationMethod,
+}
+
+impl<I: Ord + Copy, T: Clone> FillPolicy<I, T> for StackSparseFill {
+    fn fill(&self, context: FillContext<'_, I, T>) -> T {
+        let value = if context.is_extrapolation() {
+            match self.extrapolation {
+                ExtrapolationMethod::Nearest => context
+                    .previous
+                    .or(context.next)
+                    .map_or(context.default, |(_, v)| v),
+                ExtrapolationMethod::Default => context.default,
+            }
+        } else {
+            match self.interpolation {
+                InterpolationMethod::Nearest | InterpolationMethod::Previous => {
+                    context.previous_or_default()
+                }
+                InterpolationMethod::Next => context.next_or_default(),
+                InterpolationMethod::Default => context.default,
+            }
+        };
+        value.clone()
+    }
+}
+
+/// Frames stacked along a new outer dimension that numbers them, over their common inner
+/// index `I`.
+pub type Stacked<I, T> = DataFrame<CompoundIndex<HList![NumericRangeIndex<usize>, I]>, Vec<T>>;
+
+/// Sparse series merged into one wide frame, with a row per key `I` and a column per series
+/// label `L`, as built by [`DataFrame::merge_frames`].
+pub type Merged<I, L, T> =
+    DataFrame<CompoundIndex<HList![SparseNumericIndex<I>, CategoricalRange<L>]>, Vec<T>>;
+
+/// Reasons why [`DataFrame::checked_stack`] can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackError {
+    /// No frames were given.
+    Empty,
+    /// The frame at `position` has a different index than the first frame.
+    IndexMismatch {
+        /// The position of the offending frame in the input.
+        position: usize,
+    },
+    /// The frame at `position` holds a different number of elements than its index.
+    InvalidFrame {
+        /// The position of the offending frame in the input.
+        position: usize,
+    },
+}
+
+impl std::fmt::Display for StackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StackError::Empty => write!(f, "no frames to stack"),
+            StackError::IndexMismatch { position } => {
+                write!(f, "frame {} has a mismatched index", position)
+            }
+            StackError::InvalidFrame { position } => write!(
+                f,
+                "frame {} holds a different number of elements than its index",
+                position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StackError {}
+
 impl<I, D> DataFrame<I, D>
 where
     I: VariableRange + Clone + PartialEq,
@@ -61,9 +139,7 @@ where
     /// // Data order is row-major by outer index then inner index.
     /// assert_eq!(stacked.data(), &vec![10, 20, 30, 40, 50, 60]);
     /// ```
-    pub fn stack(
-        dfs: impl IntoIterator<Item = DataFrame<I, D>>,
-    ) -> Option<DataFrame<CompoundIndex<HList![NumericRangeIndex<usize>, I]>, Vec<D::Output>>> {
+    pub fn stack(dfs: impl IntoIterator<Item = DataFrame<I, D>>) -> Option<Stacked<I, D::Output>> {
         let dfs: Vec<DataFrame<I, D>> = dfs.into_iter().collect();
         if dfs.is_empty() {
             return None;
@@ -87,6 +163,93 @@ where
         }
         Some(DataFrame::new(compound_index, data))
     }
+
+    /// Like [`DataFrame::stack`], but validates the input instead of panicking.
+    ///
+    /// Every frame is checked to hold exactly as many elements as its index before any data
+    /// is copied, so frames assembled without going through [`DataFrame::new`] cannot
+    /// corrupt the stacked result.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::data_frame::stack::StackError;
+    ///
+    /// let idx = NumericRangeIndex::<i32>::new(0, 2);
+    /// let a = DataFrame::new(idx.clone(), vec![1, 2]);
+    /// let b = DataFrame::new(NumericRangeIndex::<i32>::new(0, 1), vec![3]);
+    /// assert_eq!(
+    ///     DataFrame::checked_stack([a, b]).unwrap_err(),
+    ///     StackError::IndexMismatch { position: 1 }
+    /// );
+    /// ```
+    pub fn checked_stack(
+        dfs: impl IntoIterator<Item = DataFrame<I, D>>,
+    ) -> Result<Stacked<I, D::Output>, StackError> {
+        let dfs: Vec<DataFrame<I, D>> = dfs.into_iter().collect();
+        let first_index = dfs.first().ok_or(StackError::Empty)?.index();
+        for (position, df) in dfs.iter().enumerate() {
+            if df.data().len() != df.index().size() {
+                return Err(StackError::InvalidFrame { position });
+            }
+            if df.index() != first_index {
+                return Err(StackError::IndexMismatch { position });
+            }
+        }
+        Ok(Self::stack(dfs).expect("input is not empty"))
+    }
+}
+
+impl<I, D> DataFrame<I, D>
+where
+    I: VariableRange + Clone,
+    D: FrameData,
+    D::Output: Clone,
+{
+    /// Stack DataFrames whose inner indices yield the same sequence of values, without
+    /// requiring the indices themselves to be equal.
+    ///
+    /// This allows stacking frames over structurally different indices that describe the same
+    /// keys, such as a numeric range and a sparse index over a [`DynIndex`]. The inner index
+    /// of the result is taken from the first frame.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex, SparseNumericIndex};
+    /// use slice_and_dice::mapped_index::dyn_index::DynIndex;
+    ///
+    /// let a = DataFrame::new(DynIndex::Numeric(NumericRangeIndex::new(0, 2)), vec![1, 2]);
+    /// let b = DataFrame::new(DynIndex::Sparse(SparseNumericIndex::new(vec![0, 1].into())), vec![3, 4]);
+    /// let stacked = DataFrame::stack_by_values([a, b]).unwrap();
+    /// assert_eq!(stacked.data(), &vec![1, 2, 3, 4]);
+    /// ```
+    ///
+    /// [`DynIndex`]: crate::mapped_index::dyn_index::DynIndex
+    pub fn stack_by_values(
+        dfs: impl IntoIterator<Item = DataFrame<I, D>>,
+    ) -> Result<Stacked<I, D::Output>, StackError>
+    where
+        for<'a> I::Value<'a>: PartialEq,
+    {
+        let dfs: Vec<DataFrame<I, D>> = dfs.into_iter().collect();
+        let first_index = dfs.first().ok_or(StackError::Empty)?.index();
+        let mut data = Vec::new();
+        for (position, df) in dfs.iter().enumerate() {
+            if df.data().len() != df.index().size() {
+                return Err(StackError::InvalidFrame { position });
+            }
+            if df.index().size() != first_index.size() || !df.index().iter().eq(first_index.iter())
+            {
+                return Err(StackError::IndexMismatch { position });
+            }
+            data.extend(df.data().iter().cloned());
+        }
+
+        let compound_index = CompoundIndex {
+            indices: hlist![NumericRangeIndex::new(0, dfs.len()), first_index.clone()],
+        };
+        Ok(DataFrame::new(compound_index, data))
+    }
 }
 
 impl<I, D> DataFrame<SparseNumericIndex<I>, D>
@@ -141,12 +304,44 @@ where
         interpolation: InterpolationMethod,
         extrapolation: ExtrapolationMethod,
         default_value: D::Output,
-    ) -> Option<
-        DataFrame<
-            CompoundIndex<HList![NumericRangeIndex<usize>, SparseNumericIndex<I>]>,
-            Vec<D::Output>,
-        >,
-    > {
+    ) -> Option<Stacked<SparseNumericIndex<I>, D::Output>> {
+        Self::stack_sparse_with(
+            dfs,
+            StackSparseFill {
+                interpolation,
+                extrapolation,
+            },
+            default_value,
+        )
+    }
+
+    /// Stack an iterator of DataFrames with potentially mismatching SparseNumericIndex into one
+    /// DataFrame with a compound index, filling missing values using a custom [`FillPolicy`].
+    ///
+    /// This is the extensible form of [`DataFrame::stack_sparse`]: the policy decides the value
+    /// at every key of the union index that a frame does not have.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use slice_and_dice::data_frame::fill::NextFill;
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let a = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![1_i64, 3])), vec![10, 30]);
+    /// let b = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![2_i64])), vec![20]);
+    ///
+    /// let stacked = DataFrame::stack_sparse_with([a, b], NextFill, 0).unwrap();
+    /// assert_eq!(stacked.data(), &vec![10, 30, 30, 20, 20, 0]);
+    /// ```
+    pub fn stack_sparse_with<P>(
+        dfs: impl IntoIterator<Item = DataFrame<SparseNumericIndex<I>, D>>,
+        policy: P,
+        default_value: D::Output,
+    ) -> Option<Stacked<SparseNumericIndex<I>, D::Output>>
+    where
+        P: FillPolicy<I, D::Output>,
+    {
         let dfs: Vec<DataFrame<SparseNumericIndex<I>, D>> = dfs.into_iter().collect();
         if dfs.is_empty() {
             return None;
@@ -163,84 +358,92 @@ where
 
         // Create the outer index
         let outer_index = NumericRangeIndex::new(0, dfs.len());
-        let compound_index = CompoundIndex {
-            indices: hlist![outer_index, union_index.clone()],
-        };
 
         // Fill in the data, handling missing values
         let mut data = Vec::new();
         for df in &dfs {
-            let df_indices = &df.index().indices;
-
-            for &union_idx in &union_index.indices {
-                // Check if this index exists in the current DataFrame
-                match df_indices.binary_search(&union_idx) {
-                    Ok(pos) => {
-                        // Index exists, use the actual value
-                        data.push(df.data_at(pos).clone());
-                    }
-                    Err(insert_pos) => {
-                        // Index doesn't exist, interpolate or extrapolate
-                        let value = if insert_pos == 0 || insert_pos == df_indices.len() {
-                            // Extrapolation case
-                            match extrapolation {
-                                ExtrapolationMethod::Nearest => {
-                                    if df_indices.is_empty() {
-                                        // If the DataFrame has no indices, use the default value
-                                        default_value.clone()
-                                    } else if insert_pos == 0 {
-                                        // Use the first value
-                                        df.data_at(0).clone()
-                                    } else {
-                                        // Use the last value
-                                        df.data_at(df_indices.len() - 1).clone()
-                                    }
-                                }
-                                ExtrapolationMethod::Default => default_value.clone(),
-                            }
-                        } else {
-                            // Interpolation case
-                            match interpolation {
-                                InterpolationMethod::Nearest => {
-                                    // Find the nearest index
-                                    let prev_idx = df_indices[insert_pos - 1];
-                                    let next_idx = df_indices[insert_pos];
-
-                                    // Compare distances without casting
-                                    let prev_dist = match union_idx.cmp(&prev_idx) {
-                                        Ordering::Less => prev_idx.cmp(&union_idx),
-                                        _ => union_idx.cmp(&prev_idx),
-                                    };
-
-                                    let next_dist = match union_idx.cmp(&next_idx) {
-                                        Ordering::Less => next_idx.cmp(&union_idx),
-                                        _ => union_idx.cmp(&next_idx),
-                                    };
-
-                                    match prev_dist.cmp(&next_dist) {
-                                        Ordering::Less | Ordering::Equal => {
-                                            df.data_at(insert_pos - 1).clone()
-                                        }
-                                        Ordering::Greater => df.data_at(insert_pos).clone(),
-                                    }
-                                }
-                                InterpolationMethod::Previous => {
-                                    // Use the previous value
-                                    df.data_at(insert_pos - 1).clone()
-                                }
-                                InterpolationMethod::Next => {
-                                    // Use the next value
-                                    df.data_at(insert_pos).clone()
-                                }
-                                InterpolationMethod::Default => default_value.clone(),
-                            }
-                        };
-                        data.push(value);
-                    }
-                }
-            }
+            data.extend(fill_sparse(
+                df,
+                union_index.indices.iter().copied(),
+                &policy,
+                &default_value,
+            ));
+        }
+
+        let compound_index = CompoundIndex {
+            indices: hlist![outer_index, union_index],
+        };
+        Some(DataFrame::new(compound_index, data))
+    }
+
+    /// Merge labelled sparse series into one wide frame with a row per key and a column per
+    /// series, filling missing values using a [`FillPolicy`].
+    ///
+    /// This is [`DataFrame::stack_sparse_with`] transposed, with the series dimension
+    /// labelled: the index is the union of all keys, followed by the series labels in input
+    /// order. Returns `None` if no series are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use slice_and_dice::{DataFrame, SparseNumericIndex};
+    /// use slice_and_dice::data_frame::fill::DefaultFill;
+    /// use sorted_vec::SortedSet;
+    ///
+    /// let a = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![1_i64, 2])), vec![10, 20]);
+    /// let b = DataFrame::new(SparseNumericIndex::new(SortedSet::from(vec![2_i64])), vec![200]);
+    ///
+    /// let wide = DataFrame::merge_frames([("a", a), ("b", b)], DefaultFill, 0).unwrap();
+    /// // Rows are keys 1 and 2, columns are series "a" and "b"
+    /// assert_eq!(wide.data(), &vec![10, 0, 20, 200]);
+    /// ```
+    pub fn merge_frames<L, P>(
+        series: impl IntoIterator<Item = (L, DataFrame<SparseNumericIndex<I>, D>)>,
+        policy: P,
+        default_value: D::Output,
+    ) -> Option<Merged<I, L, D::Output>>
+    where
+        L: Sync + Clone,
+        P: FillPolicy<I, D::Output>,
+    {
+        let (labels, dfs): (Vec<L>, Vec<DataFrame<SparseNumericIndex<I>, D>>) =
+            series.into_iter().unzip();
+        if dfs.is_empty() {
+            return None;
+        }
+
+        let mut all_indices = SortedSet::new();
+        for df in &dfs {
+            all_indices.extend(df.index().indices.iter().copied());
         }
+        let union_index = SparseNumericIndex::new(all_indices);
+
+        let n_keys = union_index.size();
+        // Each column yields one value per key, so taking one from each in turn lays the
+        // data out key-major. The columns borrow the union index, so they are scoped to
+        // this block.
+        let data = {
+            let mut columns: Vec<_> = dfs
+                .iter()
+                .map(|df| {
+                    fill_sparse(
+                        df,
+                        union_index.indices.iter().copied(),
+                        &policy,
+                        &default_value,
+                    )
+                })
+                .collect();
+            let mut data = Vec::with_capacity(n_keys * columns.len());
+            for _ in 0..n_keys {
+                data.extend(columns.iter_mut().filter_map(Iterator::next));
+            }
+            data
+        };
 
+        let compound_index = CompoundIndex {
+            indices: hlist![union_index, CategoricalRange::new(labels)],
+        };
         Some(DataFrame::new(compound_index, data))
     }
 }
@@ -263,6 +466,67 @@ mod tests {
         assert_eq!(stacked.data(), &vec![10, 20, 30, 40]); // Flattened data
     }
 
+    #[test]
+    fn test_checked_stack() {
+        let index = NumericRangeIndex::<i32>::new(0, 2);
+        let df1 = DataFrame::new(index.clone(), vec![10, 20]);
+        let df2 = DataFrame::new(index.clone(), vec![30, 40]);
+
+        let stacked = DataFrame::checked_stack(vec![df1, df2]).unwrap();
+        assert_eq!(stacked.data(), &vec![10, 20, 30, 40]);
+
+        let none: Vec<DataFrame<NumericRangeIndex<i32>, Vec<i32>>> = vec![];
+        assert_eq!(
+            DataFrame::checked_stack(none).unwrap_err(),
+            StackError::Empty
+        );
+    }
+
+    #[test]
+    fn test_checked_stack_invalid_frame() {
+        let index = NumericRangeIndex::<i32>::new(0, 2);
+        let valid = DataFrame::new(index.clone(), vec![10, 20]);
+        // Bypass `new` to build a frame whose data does not match its index
+        let invalid = DataFrame {
+            index: index.clone(),
+            data: vec![30],
+        };
+
+        assert_eq!(
+            DataFrame::checked_stack(vec![valid, invalid]).unwrap_err(),
+            StackError::InvalidFrame { position: 1 }
+        );
+    }
+
+    #[test]
+    fn test_stack_by_values() {
+        use crate::mapped_index::dyn_index::DynIndex;
+
+        let dense = DataFrame::new(
+            DynIndex::Numeric(NumericRangeIndex::new(0, 3)),
+            vec![1, 2, 3],
+        );
+        let sparse = DataFrame::new(
+            DynIndex::Sparse(SparseNumericIndex::new(vec![0, 1, 2].into())),
+            vec![4, 5, 6],
+        );
+
+        let stacked = DataFrame::stack_by_values(vec![dense.clone(), sparse]).unwrap();
+
+        assert_eq!(stacked.index().indices.head, NumericRangeIndex::new(0, 2));
+        assert_eq!(stacked.index().indices.tail.head, dense.index().clone());
+        assert_eq!(stacked.data(), &vec![1, 2, 3, 4, 5, 6]);
+
+        let shifted = DataFrame::new(
+            DynIndex::Sparse(SparseNumericIndex::new(vec![0, 1, 3].into())),
+            vec![7, 8, 9],
+        );
+        assert_eq!(
+            DataFrame::stack_by_values(vec![dense, shifted]).unwrap_err(),
+            StackError::IndexMismatch { position: 1 }
+        );
+    }
+
     #[test]
     fn test_stack_sparse_matching() {
         // Test with matching indices
@@ -396,6 +660,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stack_sparse_with_custom_policy() {
+        use crate::data_frame::fill::FillContext;
+
+        // A policy that ignores its context entirely
+        struct AlwaysFortyTwo;
+
+        impl FillPolicy<i32, i32> for AlwaysFortyTwo {
+            fn fill(&self, _context: FillContext<'_, i32, i32>) -> i32 {
+                42
+            }
+        }
+
+        let index1 = SparseNumericIndex::<i32>::new(vec![1, 3, 5].into());
+        let index2 = SparseNumericIndex::<i32>::new(vec![2, 3, 6].into());
+        let df1 = DataFrame::new(index1, vec![10, 30, 50]);
+        let df2 = DataFrame::new(index2, vec![20, 30, 60]);
+
+        let stacked = DataFrame::stack_sparse_with(vec![df1, df2], AlwaysFortyTwo, 0).unwrap();
+
+        // Union of indices should be [1, 2, 3, 5, 6]; every gap is filled with 42,
+        // both between present keys and beyond either end.
+        assert_eq!(
+            stacked.index().indices.tail.head.indices,
+            vec![1, 2, 3, 5, 6].into()
+        );
+        assert_eq!(
+            stacked.data(),
+            &vec![10, 42, 30, 50, 42, 42, 20, 30, 42, 60]
+        );
+    }
+
+    #[test]
+    fn test_merge_frames() {
+        use crate::data_frame::fill::PreviousFill;
+
+        let temperature = DataFrame::new(
+            SparseNumericIndex::<i32>::new(vec![1, 3].into()),
+            vec![20, 22],
+        );
+        let humidity = DataFrame::new(
+            SparseNumericIndex::<i32>::new(vec![2, 3].into()),
+            vec![60, 65],
+        );
+        let wind = DataFrame::new(SparseNumericIndex::<i32>::new(vec![1].into()), vec![5]);
+
+        let wide = DataFrame::merge_frames(
+            vec![
+                ("temperature", temperature),
+                ("humidity", humidity),
+                ("wind", wind),
+            ],
+            PreviousFill,
+            -1,
+        )
+        .unwrap();
+
+        assert_eq!(wide.index().indices.head.indices, vec![1, 2, 3].into());
+        assert_eq!(
+            wide.index().indices.tail.head,
+            CategoricalRange::new(vec!["temperature", "humidity", "wind"])
+        );
+        // One row per key: [temperature, humidity, wind]
+        assert_eq!(wide.data(), &vec![20, -1, 5, 20, 60, 5, 22, 65, 5]);
+    }
+
+    #[test]
+    fn test_stack_sparse_with_builtin_policies() {
+        use crate::data_frame::fill::{DefaultFill, NearestFill, PreviousFill, SplitFill};
+
+        let index1 = SparseNumericIndex::<i32>::new(vec![1, 3, 5].into());
+        let index2 = SparseNumericIndex::<i32>::new(vec![2, 3, 6].into());
+        let dfs = vec![
+            DataFrame::new(index1, vec![10, 30, 50]),
+            DataFrame::new(index2, vec![20, 30, 60]),
+        ];
+
+        // stack_sparse's Nearest interpolation keeps the previous value
+        let with_enums = DataFrame::stack_sparse(
+            dfs.clone(),
+            InterpolationMethod::Nearest,
+            ExtrapolationMethod::Default,
+            -1,
+        )
+        .unwrap();
+        let with_previous = DataFrame::stack_sparse_with(
+            dfs.clone(),
+            SplitFill {
+                interpolation: PreviousFill,
+                extrapolation: DefaultFill,
+            },
+            -1,
+        )
+        .unwrap();
+        assert_eq!(with_enums.data(), with_previous.data());
+
+        // NearestFill picks the closer neighbour: key 5 is closer to 6 than to 3
+        let with_nearest = DataFrame::stack_sparse_with(
+            dfs,
+            SplitFill {
+                interpolation: NearestFill,
+                extrapolation: DefaultFill,
+            },
+            -1,
+        )
+        .unwrap();
+        assert_eq!(
+            with_nearest.data(),
+            &vec![10, 10, 30, 50, -1, -1, 20, 30, 60, 60]
+        );
+    }
+
     #[test]
     fn test_stack_sparse_large_matching() {
         // Test with large matching indices (at least 10 elements each)
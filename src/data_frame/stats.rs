@@ -0,0 +1,298 @@
+//! Statistical routines over DataFrame values.
+use super::core::DataFrame;
+use crate::mapped_index::VariableRange;
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::numeric_range::NumericRangeIndex;
+use num_traits::Zero;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Add;
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange,
+{
+    /// Count the occurrences of each distinct value, indexed by the values and ordered by
+    /// descending count. Values with equal counts keep the order in which they first appear.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![true, false, false, true]);
+    /// let counts = df.value_counts();
+    /// assert_eq!(counts.index().values, vec![true, false]);
+    /// assert_eq!(counts.data(), &vec![2, 2]);
+    /// ```
+    pub fn value_counts(&self) -> DataFrame<CategoricalRange<T>, Vec<usize>>
+    where
+        T: Eq + Hash + Clone + Sync,
+    {
+        let mut positions = HashMap::new();
+        let mut counts: Vec<(&T, usize)> = Vec::new();
+        for value in &self.data {
+            let pos = *positions.entry(value).or_insert_with(|| {
+                counts.push((value, 0));
+                counts.len() - 1
+            });
+            counts[pos].1 += 1;
+        }
+        // Stable sort, so ties stay in first-seen order.
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+        let (values, counts): (Vec<T>, Vec<usize>) =
+            counts.into_iter().map(|(v, c)| (v.clone(), c)).unzip();
+        DataFrame::new(CategoricalRange::new(values), counts)
+    }
+
+    /// Sum the values of each group, where `key` assigns every value to a group.
+    ///
+    /// The result is indexed by the group keys in the order in which they first appear.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1.5, 10.0, 2.5]);
+    /// let sums = df.group_sum(|v| *v < 5.0);
+    /// assert_eq!(sums.index().values, vec![true, false]);
+    /// assert_eq!(sums.data(), &vec![4.0, 10.0]);
+    /// ```
+    pub fn group_sum<K, F>(&self, mut key: F) -> DataFrame<CategoricalRange<K>, Vec<T>>
+    where
+        K: Eq + Hash + Clone + Sync,
+        T: Copy + Zero + Add<Output = T>,
+        F: FnMut(&T) -> K,
+    {
+        let mut positions = HashMap::new();
+        let mut keys = Vec::new();
+        let mut sums: Vec<T> = Vec::new();
+        for value in &self.data {
+            let pos = *positions.entry(key(value)).or_insert_with_key(|k| {
+                keys.push(k.clone());
+                sums.push(T::zero());
+                sums.len() - 1
+            });
+            sums[pos] = sums[pos] + *value;
+        }
+        DataFrame::new(CategoricalRange::new(keys), sums)
+    }
+
+    /// Count the distinct values.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec!['x', 'y', 'x']);
+    /// assert_eq!(df.n_unique(), 2);
+    /// ```
+    pub fn n_unique(&self) -> usize
+    where
+        T: Eq + Hash,
+    {
+        self.data.iter().collect::<HashSet<_>>().len()
+    }
+
+    /// The distinct values, in the order in which they first appear.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec!['y', 'x', 'y']);
+    /// assert_eq!(df.distinct(), vec!['y', 'x']);
+    /// ```
+    pub fn distinct(&self) -> Vec<T>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut seen = HashSet::new();
+        self.data
+            .iter()
+            .filter(|v| seen.insert(*v))
+            .cloned()
+            .collect()
+    }
+}
+
+impl DataFrame<NumericRangeIndex<usize>, Vec<f64>> {
+    /// Normalized cross-correlation with `other` for lags `-max_lag..=max_lag`, indexed by lag.
+    ///
+    /// At lag `k`, each value `self[t]` is paired with `other[t + k]` where both exist.
+    /// Values are centred on their means and the sum of products is divided by the
+    /// product of the norms, so the auto-correlation at lag 0 is 1. If either series is
+    /// constant the result is NaN.
+    ///
+    /// # Panics
+    /// Panics if the frames have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let a = DataFrame::new(NumericRangeIndex::new(0, 4), vec![0.0, 1.0, 0.0, 0.0]);
+    /// let b = DataFrame::new(NumericRangeIndex::new(0, 4), vec![0.0, 0.0, 1.0, 0.0]);
+    /// let xc = a.cross_correlate(&b, 1);
+    /// assert_eq!(xc.index().start, -1);
+    /// // `b` is `a` delayed by one step, so the best match is at lag 1.
+    /// let best = xc.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+    /// assert_eq!(best.0, 1);
+    /// ```
+    pub fn cross_correlate(
+        &self,
+        other: &Self,
+        max_lag: usize,
+    ) -> DataFrame<NumericRangeIndex<i64>, Vec<f64>> {
+        assert_eq!(
+            self.n_rows(),
+            other.n_rows(),
+            "Cross-correlated frames must have the same length"
+        );
+        let x = centred(&self.data);
+        let y = centred(&other.data);
+        let norm = (dot(&x, &x) * dot(&y, &y)).sqrt();
+
+        let max_lag = max_lag as i64;
+        let index = NumericRangeIndex::new(-max_lag, max_lag + 1);
+        let data = (-max_lag..=max_lag)
+            .map(|lag| {
+                let (x, y) = if lag >= 0 {
+                    (&x[..], y.get(lag as usize..).unwrap_or_default())
+                } else {
+                    (x.get((-lag) as usize..).unwrap_or_default(), &y[..])
+                };
+                dot(x, y) / norm
+            })
+            .collect();
+        DataFrame::new(index, data)
+    }
+}
+
+impl<I> DataFrame<I, Vec<f64>>
+where
+    I: VariableRange + PartialEq,
+{
+    /// Pearson correlation coefficient between the values of two aligned frames.
+    ///
+    /// Returns NaN if either frame has zero variance (including frames with fewer than
+    /// two rows), since the coefficient is undefined there.
+    ///
+    /// # Panics
+    /// Panics if the indices differ.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let idx = NumericRangeIndex::<i32>::new(0, 3);
+    /// let a = DataFrame::new(idx.clone(), vec![1.0, 2.0, 3.0]);
+    /// let b = DataFrame::new(idx, vec![3.0, 2.0, 1.0]);
+    /// assert!((a.corr(&b) + 1.0).abs() < 1e-12);
+    /// ```
+    pub fn corr(&self, other: &DataFrame<I, Vec<f64>>) -> f64 {
+        assert!(
+            self.index() == other.index(),
+            "Correlated frames must have the same index"
+        );
+        let x = centred(&self.data);
+        let y = centred(&other.data);
+        dot(&x, &y) / (dot(&x, &x) * dot(&y, &y)).sqrt()
+    }
+}
+
+/// The values minus their mean.
+fn centred(data: &[f64]) -> Vec<f64> {
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    data.iter().map(|v| v - mean).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_sum() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1, 2, 3, 4]);
+
+        let sums = df.group_sum(|v| if v % 2 == 0 { "even" } else { "odd" });
+
+        assert_eq!(sums.index(), &CategoricalRange::new(vec!["odd", "even"]));
+        assert_eq!(sums.data(), &vec![4, 6]);
+    }
+
+    #[test]
+    fn test_value_counts() {
+        let df = DataFrame::new(
+            NumericRangeIndex::<i32>::new(0, 5),
+            vec!["a", "b", "a", "a", "c"],
+        );
+
+        let counts = df.value_counts();
+
+        assert_eq!(counts.index(), &CategoricalRange::new(vec!["a", "b", "c"]));
+        assert_eq!(counts.data(), &vec![3, 1, 1]);
+    }
+
+    #[test]
+    fn test_n_unique_and_distinct() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 5), vec![1, 2, 2, 3, 1]);
+
+        assert_eq!(df.n_unique(), 3);
+        assert_eq!(df.distinct(), vec![1, 2, 3]);
+
+        let empty = DataFrame::new(
+            NumericRangeIndex::<i32> { start: 0, end: 0 },
+            Vec::<i32>::new(),
+        );
+        assert_eq!(empty.n_unique(), 0);
+        assert!(empty.distinct().is_empty());
+    }
+
+    #[test]
+    fn test_corr() {
+        let index = NumericRangeIndex::<i32>::new(0, 3);
+        let a = DataFrame::new(index.clone(), vec![1.0, 2.0, 3.0]);
+        let b = DataFrame::new(index.clone(), vec![2.0, 4.0, 6.0]);
+        assert!((a.corr(&b) - 1.0).abs() < 1e-12);
+
+        let c = DataFrame::new(index.clone(), vec![1.0, 3.0, 2.0]);
+        assert!((a.corr(&c) - 0.5).abs() < 1e-12);
+
+        // Zero variance
+        let flat = DataFrame::new(index, vec![5.0, 5.0, 5.0]);
+        assert!(a.corr(&flat).is_nan());
+    }
+
+    #[test]
+    fn test_autocorrelation_peaks_at_zero() {
+        let df = DataFrame::new(
+            NumericRangeIndex::new(0, 6),
+            vec![1.0, 3.0, 2.0, 5.0, 4.0, 0.0],
+        );
+
+        let xc = df.cross_correlate(&df, 3);
+
+        assert_eq!(xc.index(), &NumericRangeIndex::new(-3, 4));
+        assert!((xc.data()[3] - 1.0).abs() < 1e-12);
+        for (lag, value) in xc.iter() {
+            if lag != 0 {
+                assert!(*value < xc.data()[3], "lag {lag}: {value}");
+            }
+        }
+        // Auto-correlation is symmetric in the lag
+        for k in 0..3 {
+            assert!((xc.data()[k] - xc.data()[6 - k]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cross_correlate_lag_beyond_length() {
+        let a = DataFrame::new(NumericRangeIndex::new(0, 2), vec![1.0, 2.0]);
+        let b = DataFrame::new(NumericRangeIndex::new(0, 2), vec![2.0, 1.0]);
+
+        let xc = a.cross_correlate(&b, 2);
+
+        assert_eq!(xc.data()[0], 0.0);
+        assert!((xc.data()[2] + 1.0).abs() < 1e-12);
+        assert_eq!(xc.data()[4], 0.0);
+    }
+}
@@ -0,0 +1,469 @@
+//! Operations specific to two-dimensional compound DataFrames.
+use super::core::{DataFrame, FrameData};
+use crate::mapped_index::VariableRange;
+use crate::mapped_index::categorical_index::CategoricalRange;
+use crate::mapped_index::compound_index::CompoundIndex;
+use frunk::{HList, hlist};
+use std::fmt::Display;
+
+/// The most rows [`DataFrame::to_matrix_string`] renders before eliding the middle ones.
+const MATRIX_MAX_ROWS: usize = 10;
+/// The most columns [`DataFrame::to_matrix_string`] renders before eliding the middle ones.
+const MATRIX_MAX_COLS: usize = 8;
+
+/// A two-dimensional frame with rows indexed by `R` and columns indexed by `C`.
+pub type Matrix<R, C, T> = DataFrame<CompoundIndex<HList![R, C]>, Vec<T>>;
+
+impl<A, B, D> DataFrame<CompoundIndex<HList![A, B]>, D>
+where
+    A: VariableRange,
+    B: VariableRange,
+    D: FrameData,
+{
+    /// Iterate over the columns (the inner dimension), materializing each as an owned
+    /// 1D DataFrame indexed by the outer dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+    /// let columns: Vec<Vec<i32>> = df.columns().map(|c| c.data().clone()).collect();
+    /// assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// ```
+    pub fn columns(&self) -> impl Iterator<Item = DataFrame<A, Vec<D::Output>>> + '_
+    where
+        D::Output: Clone,
+    {
+        let outer = &self.index.indices.head;
+        let n_rows = outer.size();
+        let n_cols = self.index.indices.tail.head.size();
+        (0..n_cols).map(move |col| {
+            let data = (0..n_rows)
+                .map(|row| self.data[row * n_cols + col].clone())
+                .collect();
+            DataFrame::new(outer.clone(), data)
+        })
+    }
+
+    /// Render the frame as a grid with one line per row, right-aligning each column.
+    ///
+    /// Frames with more than 10 rows or 8 columns show only the first and last few, with
+    /// `...` in place of the rest.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 20, 300, 4]);
+    /// assert_eq!(df.to_matrix_string(), "  1  20\n300   4");
+    /// ```
+    pub fn to_matrix_string(&self) -> String
+    where
+        D::Output: Display,
+    {
+        self.render_matrix(None, None)
+    }
+
+    /// Like [`DataFrame::to_matrix_string`], with a header line of column labels and each
+    /// row prefixed by its label.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     CategoricalRange::new(vec!["x", "y"]),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 20, 300, 4]);
+    /// assert_eq!(df.to_labelled_matrix_string(), "     0   1\nx    1  20\ny  300   4");
+    /// ```
+    pub fn to_labelled_matrix_string(&self) -> String
+    where
+        D::Output: Display,
+        for<'a> A::Value<'a>: Display,
+        for<'a> B::Value<'a>: Display,
+    {
+        let rows = &self.index.indices.head;
+        let cols = &self.index.indices.tail.head;
+        let row_labels = elided(rows.size(), MATRIX_MAX_ROWS)
+            .map(|row| {
+                row.map_or("...".to_string(), |r| {
+                    rows.unflatten_index_value(r).to_string()
+                })
+            })
+            .collect();
+        let col_labels = elided(cols.size(), MATRIX_MAX_COLS)
+            .map(|col| {
+                col.map_or("...".to_string(), |c| {
+                    cols.unflatten_index_value(c).to_string()
+                })
+            })
+            .collect();
+        self.render_matrix(Some(row_labels), Some(col_labels))
+    }
+
+    /// Lay out the (possibly elided) values as a grid, with optional labels for the shown
+    /// rows and columns.
+    fn render_matrix(
+        &self,
+        row_labels: Option<Vec<String>>,
+        col_labels: Option<Vec<String>>,
+    ) -> String
+    where
+        D::Output: Display,
+    {
+        let n_rows = self.index.indices.head.size();
+        let n_cols = self.index.indices.tail.head.size();
+
+        let header = usize::from(col_labels.is_some());
+        let mut lines: Vec<Vec<String>> = Vec::new();
+        lines.extend(col_labels);
+        for row in elided(n_rows, MATRIX_MAX_ROWS) {
+            lines.push(
+                elided(n_cols, MATRIX_MAX_COLS)
+                    .map(|col| match (row, col) {
+                        (Some(r), Some(c)) => self.data[r * n_cols + c].to_string(),
+                        _ => "...".to_string(),
+                    })
+                    .collect(),
+            );
+        }
+
+        let n_shown = elided(n_cols, MATRIX_MAX_COLS).count();
+        let widths: Vec<usize> = (0..n_shown)
+            .map(|c| lines.iter().map(|line| line[c].len()).max().unwrap_or(0))
+            .collect();
+        let label_width = row_labels
+            .iter()
+            .flatten()
+            .map(String::len)
+            .max()
+            .unwrap_or(0);
+
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let cells = line
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{:>width$}", cell));
+                match &row_labels {
+                    Some(labels) => {
+                        // The header line has no row label of its own.
+                        let label = i.checked_sub(header).map_or("", |r| labels[r].as_str());
+                        std::iter::once(format!("{:<label_width$}", label))
+                            .chain(cells)
+                            .collect::<Vec<_>>()
+                            .join("  ")
+                    }
+                    None => cells.collect::<Vec<_>>().join("  "),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The positions of `0..n` to show when at most `max` fit, with `None` standing in for the
+/// elided middle.
+fn elided(n: usize, max: usize) -> impl Iterator<Item = Option<usize>> + Clone {
+    let (head, tail) = if n <= max {
+        (n, 0)
+    } else {
+        (max / 2, max - max / 2)
+    };
+    let gap = (n > max).then_some(None);
+    (0..head)
+        .map(Some)
+        .chain(gap)
+        .chain((n - tail..n).map(Some))
+}
+
+impl<A, B, T> DataFrame<CompoundIndex<HList![A, B]>, Vec<T>>
+where
+    A: VariableRange + Clone,
+    B: VariableRange,
+{
+    /// Apply a function to each row (the contiguous run of values along the inner dimension),
+    /// producing a 1D DataFrame indexed by the outer dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+    /// let sums = df.apply_rows(|row| row.iter().sum::<i32>());
+    /// assert_eq!(sums.data(), &vec![6, 15]);
+    /// ```
+    pub fn apply_rows<R, F>(&self, mut f: F) -> DataFrame<A, Vec<R>>
+    where
+        F: FnMut(&[T]) -> R,
+    {
+        let outer = &self.index.indices.head;
+        let n_cols = self.index.indices.tail.head.size();
+        let data = (0..outer.size())
+            .map(|row| f(&self.data[row * n_cols..(row + 1) * n_cols]))
+            .collect();
+        DataFrame::new(outer.clone(), data)
+    }
+}
+
+impl<A, D> DataFrame<CompoundIndex<HList![A, CategoricalRange<String>]>, D>
+where
+    A: VariableRange,
+    D: FrameData,
+{
+    /// Rename a column label in the inner categorical dimension, leaving the data untouched.
+    ///
+    /// Only the first matching label is renamed. Returns whether `old` was found.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    ///     CategoricalRange::new(vec!["x".to_string(), "y".to_string()]),
+    /// ]);
+    /// let mut df = DataFrame::new(index, vec![1, 2]);
+    /// assert!(df.rename_column("y", "z".to_string()));
+    /// assert!(!df.rename_column("missing", "w".to_string()));
+    /// ```
+    pub fn rename_column(&mut self, old: &str, new: String) -> bool {
+        match self
+            .index
+            .indices
+            .tail
+            .head
+            .values
+            .iter_mut()
+            .find(|label| label.as_str() == old)
+        {
+            Some(label) => {
+                *label = new;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<A, B> DataFrame<CompoundIndex<HList![A, B]>, Vec<f64>>
+where
+    A: VariableRange,
+    B: VariableRange,
+{
+    /// The sample covariance matrix of the columns, treating rows as samples and columns
+    /// as variables.
+    ///
+    /// Uses the unbiased `n - 1` denominator, so every entry is NaN if there are fewer
+    /// than two rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::CompoundIndex;
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![0.0, 0.0, 2.0, -2.0]);
+    /// assert_eq!(df.covariance_matrix().data(), &vec![2.0, -2.0, -2.0, 2.0]);
+    /// ```
+    pub fn covariance_matrix(&self) -> Matrix<B, B, f64> {
+        let n_rows = self.index.indices.head.size();
+        let columns = &self.index.indices.tail.head;
+        let n_cols = columns.size();
+
+        let means: Vec<f64> = (0..n_cols)
+            .map(|c| (0..n_rows).map(|r| self.data[r * n_cols + c]).sum::<f64>() / n_rows as f64)
+            .collect();
+        let deviation = |r: usize, c: usize| self.data[r * n_cols + c] - means[c];
+
+        let data = (0..n_cols)
+            .flat_map(|i| (0..n_cols).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                (0..n_rows)
+                    .map(|r| deviation(r, i) * deviation(r, j))
+                    .sum::<f64>()
+                    / (n_rows as f64 - 1.0)
+            })
+            .collect();
+        DataFrame::new(
+            CompoundIndex::new(hlist![columns.clone(), columns.clone()]),
+            data,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+    #[test]
+    fn test_columns() {
+        // Create a 2x3 DataFrame:
+        // [1, 2, 3]
+        // [4, 5, 6]
+        let index = CompoundIndex::new(hlist![
+            CategoricalRange::new(vec!["a", "b"]),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+
+        let columns: Vec<_> = df.columns().collect();
+        assert_eq!(columns.len(), 3);
+
+        // Each column is indexed by the outer dimension
+        for column in &columns {
+            assert_eq!(column.index(), &CategoricalRange::new(vec!["a", "b"]));
+        }
+        assert_eq!(columns[0].data(), &vec![1, 4]);
+        assert_eq!(columns[1].data(), &vec![2, 5]);
+        assert_eq!(columns[2].data(), &vec![3, 6]);
+    }
+
+    #[test]
+    fn test_to_matrix_string() {
+        let index = CompoundIndex::new(hlist![
+            CategoricalRange::new(vec!["a", "bb"]),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1, -20, 3, 400, 5, 6]);
+
+        let plain = df.to_matrix_string();
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines, vec!["  1  -20  3", "400    5  6"]);
+
+        let labelled = df.to_labelled_matrix_string();
+        let lines: Vec<&str> = labelled.lines().collect();
+        assert_eq!(
+            lines,
+            vec!["      0    1  2", "a     1  -20  3", "bb  400    5  6"]
+        );
+    }
+
+    #[test]
+    fn test_to_matrix_string_elides_large_frames() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 12),
+            NumericRangeIndex::<i32>::new(0, 9)
+        ]);
+        let df = DataFrame::new(index, vec![0; 12 * 9]);
+
+        let rendered = df.to_labelled_matrix_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        // A header, 5 leading rows, the elision row and 5 trailing rows
+        assert_eq!(lines.len(), 12);
+        // Every column is as wide as the elision marker
+        assert_eq!(lines[0], "       0    1    2    3  ...    5    6    7    8");
+        assert_eq!(lines[6], ["..."; 10].join("  "));
+        assert_eq!(
+            lines[11],
+            "11     0    0    0    0  ...    0    0    0    0"
+        );
+    }
+
+    #[test]
+    fn test_apply_rows() {
+        // Create a 2x3 DataFrame:
+        // [1, 7, 3]
+        // [9, 5, 6]
+        let index = CompoundIndex::new(hlist![
+            CategoricalRange::new(vec!["a", "b"]),
+            NumericRangeIndex::<i32>::new(0, 3)
+        ]);
+        let df = DataFrame::new(index, vec![1, 7, 3, 9, 5, 6]);
+
+        let maxes = df.apply_rows(|row| *row.iter().max().unwrap());
+
+        assert_eq!(maxes.index(), &CategoricalRange::new(vec!["a", "b"]));
+        assert_eq!(maxes.data(), &vec![7, 9]);
+    }
+
+    #[test]
+    fn test_rename_column() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            CategoricalRange::new(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        ]);
+        let mut df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+
+        assert!(df.rename_column("b", "renamed".to_string()));
+        assert!(!df.rename_column("b", "again".to_string()));
+
+        let pairs: Vec<(i32, String, i32)> = df
+            .iter()
+            .map(|(v, x)| (v.head, v.tail.head.clone(), *x))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (0, "a".to_string(), 1),
+                (0, "renamed".to_string(), 2),
+                (0, "c".to_string(), 3),
+                (1, "a".to_string(), 4),
+                (1, "renamed".to_string(), 5),
+                (1, "c".to_string(), 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_covariance_matrix() {
+        // 3 samples of 2 variables:
+        // [1, 2]
+        // [2, 4]
+        // [3, 7]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 3),
+            CategoricalRange::new(vec!["x", "y"])
+        ]);
+        let df = DataFrame::new(index, vec![1.0, 2.0, 2.0, 4.0, 3.0, 7.0]);
+
+        let cov = df.covariance_matrix();
+
+        assert_eq!(
+            cov.index().indices,
+            hlist![
+                CategoricalRange::new(vec!["x", "y"]),
+                CategoricalRange::new(vec!["x", "y"])
+            ]
+        );
+        let expected = [1.0, 2.5, 2.5, 19.0 / 3.0];
+        for (actual, expected) in cov.data().iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        }
+    }
+}
@@ -0,0 +1,471 @@
+//! Sliding-window operations over DataFrames.
+use super::aggregate::strided_views;
+use super::core::{DataFrame, FrameData};
+use super::strided_index_view::StridedIndexView;
+use crate::mapped_index::compound_index::{CompoundIndex, IndexHlist};
+use crate::mapped_index::numeric_range::{NumericRangeIndex, NumericRangeValue};
+use crate::mapped_index::util::pluck_split::{PluckAt, PluckSplitImpl};
+use crate::mapped_index::{SubRange, VariableRange};
+use frunk::hlist::Selector;
+
+impl<I, T> DataFrame<NumericRangeIndex<I>, Vec<T>>
+where
+    I: NumericRangeValue,
+{
+    /// Iterate over all contiguous windows of `size` values, borrowing from the frame
+    /// without allocating, like [`slice::windows`].
+    ///
+    /// Yields nothing if the frame has fewer than `size` rows.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 4), vec![1, 2, 3, 4]);
+    /// let sums: Vec<i32> = df.windows(3).map(|w| w.iter().sum()).collect();
+    /// assert_eq!(sums, vec![6, 9]);
+    /// ```
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = &[T]> + '_ {
+        self.data.windows(size)
+    }
+}
+
+impl<I> DataFrame<NumericRangeIndex<I>, Vec<f64>>
+where
+    I: NumericRangeValue,
+{
+    /// The mean of every window of `window` consecutive values.
+    ///
+    /// Each result is labelled with the last index value of its window, so the index is
+    /// shifted forward by `window - 1`. Runs in O(n) regardless of the window size by
+    /// updating a running sum, rather than summing each window from scratch.
+    ///
+    /// # Panics
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), vec![1.0, 3.0, 5.0, 7.0]);
+    /// let means = df.rolling_mean(2);
+    /// assert_eq!(means.index(), &NumericRangeIndex::new(1, 4));
+    /// assert_eq!(means.data(), &vec![2.0, 4.0, 6.0]);
+    /// ```
+    pub fn rolling_mean(&self, window: usize) -> DataFrame<NumericRangeIndex<I>, Vec<f64>> {
+        let data = self
+            .rolling_sums(window)
+            .map(|(sum, _)| sum / window as f64)
+            .collect();
+        self.with_window_ends(window, data)
+    }
+
+    /// The sample variance (with an `n - 1` denominator) of every window of `window`
+    /// consecutive values, labelled like [`DataFrame::rolling_mean`].
+    ///
+    /// Runs in O(n) by updating a running sum and sum of squares. A window of 1 has an
+    /// undefined sample variance and yields NaN.
+    ///
+    /// # Panics
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1.0, 3.0, 7.0]);
+    /// assert_eq!(df.rolling_var(2).data(), &vec![2.0, 8.0]);
+    /// ```
+    pub fn rolling_var(&self, window: usize) -> DataFrame<NumericRangeIndex<I>, Vec<f64>> {
+        let n = window as f64;
+        let data = self
+            .rolling_sums(window)
+            // Rounding can push a zero variance slightly negative
+            .map(|(sum, sum_sq)| ((sum_sq - sum * sum / n) / (n - 1.0)).max(0.0))
+            .collect();
+        self.with_window_ends(window, data)
+    }
+
+    /// Exponentially weighted moving average: `s[0] = x[0]` and
+    /// `s[i] = alpha * x[i] + (1 - alpha) * s[i - 1]`, keeping the same index.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not in `(0, 1]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![4.0, 0.0, 0.0]);
+    /// assert_eq!(df.ewm(0.75).data(), &vec![4.0, 1.0, 0.25]);
+    /// ```
+    pub fn ewm(&self, alpha: f64) -> DataFrame<NumericRangeIndex<I>, Vec<f64>> {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "alpha must be in (0, 1], got {}",
+            alpha
+        );
+        let mut smoothed: Option<f64> = None;
+        let data = self
+            .data
+            .iter()
+            .map(|&x| {
+                let s = smoothed.map_or(x, |prev| alpha * x + (1.0 - alpha) * prev);
+                smoothed = Some(s);
+                s
+            })
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+
+    /// The running `(sum, sum of squares)` of every window of `window` values.
+    fn rolling_sums(&self, window: usize) -> impl Iterator<Item = (f64, f64)> + '_ {
+        assert!(window > 0, "window must be positive");
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        self.data.iter().enumerate().filter_map(move |(i, &x)| {
+            sum += x;
+            sum_sq += x * x;
+            if i >= window {
+                let old = self.data[i - window];
+                sum -= old;
+                sum_sq -= old * old;
+            }
+            (i + 1 >= window).then_some((sum, sum_sq))
+        })
+    }
+
+    /// Label per-window results with the index value at the end of each window.
+    fn with_window_ends(
+        &self,
+        window: usize,
+        data: Vec<f64>,
+    ) -> DataFrame<NumericRangeIndex<I>, Vec<f64>> {
+        let n_rows = self.n_rows();
+        let index = self.index.sub_range((window - 1).min(n_rows), n_rows);
+        DataFrame::new(index, data)
+    }
+}
+
+impl<I, T> DataFrame<I, Vec<T>>
+where
+    I: VariableRange + Clone,
+{
+    /// Apply `f` to every prefix of the data, so that position `i` of the result is computed
+    /// from `data[0..=i]`, keeping the same index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![2.0, 4.0, 9.0]);
+    /// let running_mean = df.expanding(|w| w.iter().sum::<f64>() / w.len() as f64);
+    /// assert_eq!(running_mean.data(), &vec![2.0, 3.0, 5.0]);
+    /// ```
+    pub fn expanding<R, F>(&self, mut f: F) -> DataFrame<I, Vec<R>>
+    where
+        F: FnMut(&[T]) -> R,
+    {
+        let data = (1..=self.data.len())
+            .map(|end| f(&self.data[..end]))
+            .collect();
+        DataFrame::new(self.index.clone(), data)
+    }
+}
+
+impl<Indices, D> DataFrame<CompoundIndex<Indices>, D>
+where
+    Indices: IndexHlist,
+    D: FrameData,
+{
+    /// Slide a window of `window` consecutive positions along the dimension specified by
+    /// typenum, yielding each window as an owned sub-frame.
+    ///
+    /// Each sub-frame keeps every other dimension in full and has the windowed dimension
+    /// cut down to the positions `start..start + window`, in the same row-major layout as
+    /// the original frame. Yields nothing if the dimension is shorter than `window`.
+    ///
+    /// # Panics
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim1};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 2),
+    ///     NumericRangeIndex::<i32>::new(0, 3),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6]);
+    /// let windows: Vec<Vec<i32>> = df
+    ///     .iter_windows_over_dim::<Dim1>(2)
+    ///     .map(|w| w.data().clone())
+    ///     .collect();
+    /// assert_eq!(windows, vec![vec![1, 2, 4, 5], vec![2, 3, 5, 6]]);
+    /// ```
+    pub fn iter_windows_over_dim<Idx>(
+        &self,
+        window: usize,
+    ) -> impl Iterator<Item = DataFrame<CompoundIndex<Indices>, Vec<D::Output>>> + '_
+    where
+        Indices: PluckSplitImpl<Idx> + Selector<PluckAt<Idx, Indices>, Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: SubRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        D::Output: Clone,
+    {
+        assert!(window > 0, "window must be positive");
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+
+        (0..(m_size + 1).saturating_sub(window)).map(move |start| {
+            let mut indices = self.index.indices.clone();
+            let dim: &mut PluckAt<Idx, Indices> = indices.get_mut();
+            *dim = dim.sub_range(start, start + window);
+
+            let data = (0..l_size)
+                .flat_map(|l_i| {
+                    let base = (l_i * m_size + start) * r_size;
+                    base..base + window * r_size
+                })
+                .map(|i| self.data[i].clone())
+                .collect();
+            DataFrame::new(CompoundIndex::new(indices), data)
+        })
+    }
+
+    /// Reduce every window of `window` consecutive positions along the dimension specified
+    /// by typenum to a single value, e.g. for a moving average or a convolution per axis.
+    /// This is [`DataFrame::rolling_mean`] and friends generalized to any axis of a compound
+    /// frame.
+    ///
+    /// The dimension shrinks to `size - window + 1` positions, each labelled with the last
+    /// position of its window; it becomes empty if it is shorter than `window`. Unlike
+    /// [`DataFrame::iter_windows_over_dim`], `f` sees zero-copy views of the data.
+    ///
+    /// # Panics
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::{DataFrame, NumericRangeIndex};
+    /// use slice_and_dice::mapped_index::compound_index::{CompoundIndex, Dim0};
+    /// use frunk::hlist;
+    ///
+    /// let index = CompoundIndex::new(hlist![
+    ///     NumericRangeIndex::<i32>::new(0, 4),
+    ///     NumericRangeIndex::<i32>::new(0, 1),
+    /// ]);
+    /// let df = DataFrame::new(index, vec![1, 2, 3, 4]);
+    /// let sums = df.windowed_over_dim::<Dim0, _, _>(3, |w| w.sum::<i32>());
+    /// assert_eq!(sums.index().indices.head, NumericRangeIndex::new(2, 4));
+    /// assert_eq!(sums.data(), &vec![6, 9]);
+    /// ```
+    pub fn windowed_over_dim<Idx, R, F>(
+        &self,
+        window: usize,
+        f: F,
+    ) -> DataFrame<CompoundIndex<Indices>, Vec<R>>
+    where
+        Indices: PluckSplitImpl<Idx> + Selector<PluckAt<Idx, Indices>, Idx>,
+        <Indices as PluckSplitImpl<Idx>>::Left: IndexHlist,
+        <Indices as PluckSplitImpl<Idx>>::Extract: SubRange,
+        <Indices as PluckSplitImpl<Idx>>::Right: IndexHlist,
+        F: for<'any> Fn(StridedIndexView<'any, D>) -> R,
+    {
+        assert!(window > 0, "window must be positive");
+        let (l, m, r) = self.index.indices.clone().pluck_split_impl();
+        let l_size = l.size();
+        let m_size = m.size();
+        let r_size = r.size();
+        let n_windows = (m_size + 1).saturating_sub(window);
+
+        let window_starts = (0..n_windows).map(|start| (start, window));
+        let data = strided_views(&self.data, (l_size, m_size, r_size), window_starts)
+            .map(f)
+            .collect();
+
+        let mut indices = self.index.indices.clone();
+        let dim: &mut PluckAt<Idx, Indices> = indices.get_mut();
+        *dim = dim.sub_range((window - 1).min(m_size), m_size);
+        DataFrame::new(CompoundIndex::new(indices), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::compound_index::{Dim0, Dim1};
+    use frunk::hlist;
+
+    #[test]
+    fn test_windows() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 4), vec![1, 2, 3, 4]);
+
+        let windows: Vec<&[i32]> = df.windows(2).collect();
+        assert_eq!(windows, vec![&[1, 2][..], &[2, 3], &[3, 4]]);
+
+        assert_eq!(df.windows(4).count(), 1);
+        assert_eq!(df.windows(5).count(), 0);
+    }
+
+    #[test]
+    fn test_expanding() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 5), vec![1, 3, 2, 5, 4]);
+
+        let running_max = df.expanding(|w| *w.iter().max().unwrap());
+
+        assert_eq!(running_max.index(), df.index());
+        assert_eq!(running_max.data(), &vec![1, 3, 3, 5, 5]);
+    }
+
+    #[test]
+    fn test_iter_windows_over_dim() {
+        // 2x4 frame:
+        // [1, 2, 3, 4]
+        // [5, 6, 7, 8]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(10, 14)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let windows: Vec<_> = df.iter_windows_over_dim::<Dim1>(2).collect();
+        assert_eq!(windows.len(), 3);
+
+        // Each window covers both rows and two consecutive columns
+        let second = &windows[1];
+        assert_eq!(
+            second.index().indices,
+            hlist![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(11, 13)]
+        );
+        let members: Vec<(i32, i32, i32)> = second
+            .iter()
+            .map(|(v, x)| (v.head, v.tail.head, *x))
+            .collect();
+        assert_eq!(
+            members,
+            vec![(0, 11, 2), (0, 12, 3), (1, 11, 6), (1, 12, 7)]
+        );
+        assert_eq!(windows[2].data(), &vec![3, 4, 7, 8]);
+
+        // Windowing the outer dimension keeps whole rows
+        let rows: Vec<_> = df.iter_windows_over_dim::<Dim0>(2).collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].data(), df.data());
+
+        assert_eq!(df.iter_windows_over_dim::<Dim0>(3).count(), 0);
+    }
+
+    #[test]
+    fn test_windowed_over_dim() {
+        // 2x4 frame:
+        // [1, 3, 5, 7]
+        // [2, 2, 8, 4]
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(10, 14)
+        ]);
+        let df = DataFrame::new(index, vec![1.0, 3.0, 5.0, 7.0, 2.0, 2.0, 8.0, 4.0]);
+
+        let means = df.windowed_over_dim::<Dim1, _, _>(2, |w| w.sum::<f64>() / 2.0);
+
+        assert_eq!(
+            means.index().indices,
+            hlist![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(11, 14)]
+        );
+        assert_eq!(means.data(), &vec![2.0, 4.0, 6.0, 2.0, 5.0, 6.0]);
+
+        // Windowing the outer dimension, and a window longer than the dimension
+        let col_sums = df.windowed_over_dim::<Dim0, _, _>(2, |w| w.sum::<f64>());
+        assert_eq!(col_sums.data(), &vec![3.0, 5.0, 13.0, 11.0]);
+        let empty = df.windowed_over_dim::<Dim1, _, _>(5, |w| w.count());
+        assert_eq!(empty.n_rows(), 0);
+    }
+
+    #[test]
+    fn test_windowed_over_dim_rolling_sum() {
+        let index = CompoundIndex::new(hlist![
+            NumericRangeIndex::<i32>::new(0, 2),
+            NumericRangeIndex::<i32>::new(0, 4)
+        ]);
+        let df = DataFrame::new(index, vec![1, 2, 3, 4, 10, 20, 30, 40]);
+
+        let sums = df.windowed_over_dim::<Dim1, _, _>(2, |w| w.sum::<i32>());
+
+        assert_eq!(sums.data(), &vec![3, 5, 7, 30, 50, 70]);
+        // Each row's rolling sum matches the 1D rolling window over that row
+        for (row, expected) in [(0, [1, 2, 3, 4]), (1, [10, 20, 30, 40])] {
+            let single = DataFrame::new(NumericRangeIndex::<i32>::new(0, 4), expected.to_vec());
+            let rolled: Vec<i32> = single.windows(2).map(|w| w.iter().sum()).collect();
+            assert_eq!(&sums.data()[row * 3..(row + 1) * 3], rolled.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_rolling_mean_matches_windows() {
+        let data: Vec<f64> = (0..50).map(|i| ((i * 29) % 13) as f64 - 6.0).collect();
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(100, 150), data);
+
+        let fast = df.rolling_mean(7);
+        let slow: Vec<f64> = df.windows(7).map(|w| w.iter().sum::<f64>() / 7.0).collect();
+
+        assert_eq!(fast.index(), &NumericRangeIndex::new(106, 150));
+        assert_eq!(fast.n_rows(), slow.len());
+        for (a, b) in fast.data().iter().zip(&slow) {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_rolling_var_matches_windows() {
+        let data: Vec<f64> = (0..40).map(|i| ((i * 17) % 11) as f64 * 0.5).collect();
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 40), data);
+
+        let fast = df.rolling_var(5);
+        let slow: Vec<f64> = df
+            .windows(5)
+            .map(|w| {
+                let mean = w.iter().sum::<f64>() / 5.0;
+                w.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 4.0
+            })
+            .collect();
+
+        assert_eq!(fast.n_rows(), slow.len());
+        for (a, b) in fast.data().iter().zip(&slow) {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_rolling_mean_window_longer_than_frame() {
+        let df = DataFrame::new(NumericRangeIndex::<usize>::new(0, 2), vec![1.0, 2.0]);
+
+        let means = df.rolling_mean(3);
+
+        assert_eq!(means.n_rows(), 0);
+        assert_eq!(means.index().size(), 0);
+    }
+
+    #[test]
+    fn test_ewm() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 3), vec![1.0, 2.0, 3.0]);
+
+        let smoothed = df.ewm(0.5);
+
+        // s0 = 1, s1 = 0.5 * 2 + 0.5 * 1 = 1.5, s2 = 0.5 * 3 + 0.5 * 1.5 = 2.25
+        assert_eq!(smoothed.index(), df.index());
+        assert_eq!(smoothed.data(), &vec![1.0, 1.5, 2.25]);
+
+        // alpha = 1 disables smoothing
+        assert_eq!(df.ewm(1.0).data(), df.data());
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be in (0, 1]")]
+    fn test_ewm_invalid_alpha() {
+        let df = DataFrame::new(NumericRangeIndex::<i32>::new(0, 1), vec![1.0]);
+        let _ = df.ewm(0.0);
+    }
+}
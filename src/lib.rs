@@ -9,3 +9,57 @@ pub use crate::data_frame::core::DataFrame;
 pub use crate::mapped_index::numeric_range::NumericRangeIndex;
 /// Sparse numeric index (arbitrary, sorted i64 indices).
 pub use crate::mapped_index::sparse_numeric_index::SparseNumericIndex;
+
+/// Build a one-dimensional DataFrame from a list of values.
+///
+/// `dataframe![a, b, c]` indexes the values by a `NumericRangeIndex<usize>` starting at 0,
+/// while `dataframe!{ "a" => x, "b" => y }` indexes them by a `CategoricalRange` of the keys.
+///
+/// # Examples
+/// ```
+/// use slice_and_dice::dataframe;
+///
+/// let df = dataframe![10, 20, 30];
+/// assert_eq!(df.index().end, 3);
+/// assert_eq!(df.data(), &vec![10, 20, 30]);
+///
+/// let df = dataframe! { "a" => 1.0, "b" => 2.0 };
+/// assert_eq!(df.index().values, vec!["a", "b"]);
+/// assert_eq!(df.data(), &vec![1.0, 2.0]);
+/// ```
+#[macro_export]
+macro_rules! dataframe {
+    ($($key:expr => $value:expr),+ $(,)?) => {
+        $crate::DataFrame::new(
+            $crate::mapped_index::categorical_index::CategoricalRange::new(vec![$($key),+]),
+            vec![$($value),+],
+        )
+    };
+    ($($value:expr),* $(,)?) => {{
+        let data = vec![$($value),*];
+        let index = <$crate::NumericRangeIndex<usize> as $crate::mapped_index::FromSize>::from_size(
+            data.len(),
+        );
+        $crate::DataFrame::new(index, data)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::categorical_index::CategoricalRange;
+
+    #[test]
+    fn test_dataframe_macro() {
+        let df = dataframe![1, 2, 3];
+        assert_eq!(df.index(), &NumericRangeIndex::<usize>::new(0, 3));
+        assert_eq!(df.data(), &vec![1, 2, 3]);
+
+        let df = dataframe! { "a" => 1, "b" => 2 };
+        assert_eq!(df.index(), &CategoricalRange::new(vec!["a", "b"]));
+        assert_eq!(df.data(), &vec![1, 2]);
+
+        let df: DataFrame<_, Vec<i32>> = dataframe![];
+        assert_eq!(df.n_rows(), 0);
+    }
+}
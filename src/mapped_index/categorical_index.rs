@@ -1,6 +1,9 @@
-use super::VariableRange;
+use super::{IntoValues, SubRange, VariableRange};
+use std::collections::HashSet;
+use std::hash::Hash;
 
 /// An index for categorical values, mapping indices to values of type `T`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CategoricalRange<T> {
     /// The values stored in the index.
@@ -55,11 +58,72 @@ impl<T: Sync + Clone> VariableRange for CategoricalRange<T> {
     }
 }
 
+impl<T: Sync + Clone> IntoValues for CategoricalRange<T> {
+    type OwnedValue = T;
+    type IntoValues = std::vec::IntoIter<T>;
+
+    fn into_values(self) -> Self::IntoValues {
+        self.values.into_iter()
+    }
+}
+
+impl<T: Sync + Clone> SubRange for CategoricalRange<T> {
+    fn sub_range(&self, start: usize, end: usize) -> Self {
+        Self::new(self.values[start..end].to_vec())
+    }
+}
+
 impl<T> CategoricalRange<T> {
     /// Create a new CategoricalIndex from a vector of values.
     pub const fn new(values: Vec<T>) -> Self {
         Self { values }
     }
+
+    /// Create an empty CategoricalRange with room for `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+
+    /// Append a category to the end of the index.
+    ///
+    /// A DataFrame checks that its index and data sizes match only when it is constructed,
+    /// so an index that is grown while in use by a frame must be paired with a matching
+    /// push to the frame's data.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// let mut idx = CategoricalRange::with_capacity(2);
+    /// idx.push("a");
+    /// idx.push("b");
+    /// assert_eq!(idx.values, vec!["a", "b"]);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+    }
+}
+
+impl<T: Eq + Hash> CategoricalRange<T> {
+    /// Returns true if any value occurs more than once, which makes lookup by value ambiguous.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::mapped_index::categorical_index::CategoricalRange;
+    /// assert!(CategoricalRange::new(vec![1, 2, 1]).has_duplicates());
+    /// assert!(!CategoricalRange::new(vec![1, 2, 3]).has_duplicates());
+    /// ```
+    pub fn has_duplicates(&self) -> bool {
+        let mut seen = HashSet::with_capacity(self.values.len());
+        !self.values.iter().all(|v| seen.insert(v))
+    }
+}
+
+/// Appends categories to the end of the index; see [`CategoricalRange::push`] for the caveat
+/// on growing an index that is in use by a DataFrame.
+impl<T> Extend<T> for CategoricalRange<T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.values.extend(iter);
+    }
 }
 
 impl<'a, T> SliceCategoricalIndex<'a, T> {
@@ -68,3 +132,33 @@ impl<'a, T> SliceCategoricalIndex<'a, T> {
         Self { values }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_extend() {
+        let mut idx = CategoricalRange::with_capacity(3);
+        idx.push("red".to_string());
+        idx.push("green".to_string());
+        idx.push("blue".to_string());
+
+        assert_eq!(idx.size(), 3);
+        assert_eq!(
+            idx.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["red", "green", "blue"]
+        );
+
+        idx.extend(["cyan".to_string(), "magenta".to_string()]);
+        assert_eq!(idx.size(), 5);
+        assert_eq!(idx.unflatten_index_value(4), "magenta");
+    }
+
+    #[test]
+    fn test_has_duplicates() {
+        assert!(CategoricalRange::new(vec!["a", "b", "a"]).has_duplicates());
+        assert!(!CategoricalRange::new(vec!["a", "b", "c"]).has_duplicates());
+        assert!(!CategoricalRange::<&str>::new(vec![]).has_duplicates());
+    }
+}
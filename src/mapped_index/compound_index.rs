@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use crate::mapped_index::VariableRange;
+use crate::mapped_index::util::pluck_split::PluckRemainder;
 use frunk::hlist::{HList, h_cons};
 use frunk::indices::{Here, There};
 use frunk::{HCons, HNil};
@@ -27,6 +28,43 @@ impl<Indices> CompoundIndex<Indices> {
     }
 }
 
+/// The index that remains after reducing away dimension `At` of a compound index over
+/// `Indices`, e.g. with `aggregate_over_dim`.
+pub type ReducedIndex<At, Indices> = CompoundIndex<PluckRemainder<At, Indices>>;
+
+impl<Indices: IndexHlist> CompoundIndex<Indices> {
+    /// The number of dimensions of the index, known at compile time.
+    pub const fn n_dims() -> usize {
+        Indices::N_DIMS
+    }
+
+    /// The position along each dimension of the given flat index, outermost first.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn unflatten_coords(&self, index: usize) -> Vec<usize>
+    where
+        Indices: IndexShape,
+    {
+        assert!(index < self.indices.size(), "Index out of bounds.");
+        let mut coords = self.indices.shape();
+        let mut rest = index;
+        for coord in coords.iter_mut().rev() {
+            let size = *coord;
+            *coord = rest % size;
+            rest /= size;
+        }
+        coords
+    }
+
+    /// The positions along each dimension of every flat index, in flat order.
+    pub fn iter_coords(&self) -> impl Iterator<Item = Vec<usize>> + '_
+    where
+        Indices: IndexShape,
+    {
+        (0..self.indices.size()).map(|index| self.unflatten_coords(index))
+    }
+}
+
 impl<A: VariableRange> CompoundIndex<(A,)> {
     pub fn collapse_single(self) -> A {
         self.indices.0
@@ -34,6 +72,9 @@ impl<A: VariableRange> CompoundIndex<(A,)> {
 }
 
 pub trait IndexHlist: HList + Sync + Clone {
+    /// The number of dimensions (sub-indices) in the list.
+    const N_DIMS: usize;
+
     type Value<'a>: Copy + HList
     where
         Self: 'a;
@@ -42,10 +83,17 @@ pub trait IndexHlist: HList + Sync + Clone {
 
     fn size(&self) -> usize;
 
+    /// Returns true if every sub-index is well formed.
+    fn is_well_formed(&self) -> bool {
+        true
+    }
+
     fn unflatten_index_value(&self, index: usize) -> Self::Value<'_>;
 }
 
 impl IndexHlist for HNil {
+    const N_DIMS: usize = 0;
+
     type Value<'a> = HNil;
 
     fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
@@ -66,6 +114,8 @@ where
     Tail: IndexHlist,
     Head: VariableRange,
 {
+    const N_DIMS: usize = 1 + Tail::N_DIMS;
+
     type Value<'a>
         = HCons<Head::Value<'a>, Tail::Value<'a>>
     where
@@ -82,6 +132,10 @@ where
         self.head.size() * self.tail.size()
     }
 
+    fn is_well_formed(&self) -> bool {
+        self.head.is_well_formed() && self.tail.is_well_formed()
+    }
+
     fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
         h_cons(
             self.head.unflatten_index_value(index / self.tail.size()),
@@ -90,6 +144,29 @@ where
     }
 }
 
+/// The size of each sub-index in a list of indices.
+///
+/// Kept separate from [`IndexHlist`] so that existing implementors of that trait are
+/// unaffected; it is implemented for every hlist of [`VariableRange`]s.
+pub trait IndexShape {
+    /// The size of each sub-index, outermost first.
+    fn shape(&self) -> Vec<usize>;
+}
+
+impl IndexShape for HNil {
+    fn shape(&self) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+impl<Head: VariableRange, Tail: IndexShape> IndexShape for HCons<Head, Tail> {
+    fn shape(&self) -> Vec<usize> {
+        let mut shape = self.tail.shape();
+        shape.insert(0, self.head.size());
+        shape
+    }
+}
+
 impl<Indices: IndexHlist> VariableRange for CompoundIndex<Indices> {
     type Value<'a>
         = <Indices as IndexHlist>::Value<'a>
@@ -107,6 +184,10 @@ impl<Indices: IndexHlist> VariableRange for CompoundIndex<Indices> {
     fn size(&self) -> usize {
         self.indices.size()
     }
+
+    fn is_well_formed(&self) -> bool {
+        self.indices.is_well_formed()
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +220,20 @@ mod tests {
         assert_eq!(compound_three.size(), 6); // 1 * 3 * 2 = 6
     }
 
+    #[test]
+    fn test_n_dims() {
+        type Three = CompoundIndex<
+            frunk::HList![
+                SingletonRange<i32>,
+                CategoricalRange<i32>,
+                NumericRangeIndex<usize>
+            ],
+        >;
+        assert_eq!(Three::n_dims(), 3);
+        assert_eq!(CompoundIndex::<HNil>::n_dims(), 0);
+        const { assert!(<frunk::HList![NumericRangeIndex<i32>]>::N_DIMS == 1) };
+    }
+
     #[test]
     fn test_compound_index_iteration() {
         // Test with a single index
@@ -193,6 +288,30 @@ mod tests {
         assert_eq!(*value2.tail.head, 3);
     }
 
+    #[test]
+    fn test_compound_index_unflatten_coords() {
+        let indices = h_cons(
+            NumericRangeIndex::<i32>::new(0, 2),
+            h_cons(CategoricalRange::new(vec!['a', 'b', 'c']), HNil),
+        );
+        let compound = CompoundIndex::new(indices);
+
+        assert_eq!(compound.unflatten_coords(0), vec![0, 0]);
+        assert_eq!(compound.unflatten_coords(2), vec![0, 2]);
+        assert_eq!(compound.unflatten_coords(4), vec![1, 1]);
+        assert_eq!(
+            compound.iter_coords().collect::<Vec<_>>(),
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 0],
+                vec![1, 1],
+                vec![1, 2]
+            ]
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_compound_index_unflatten_out_of_bounds() {
@@ -0,0 +1,201 @@
+//! Runtime-typed indices, for when the dimensions of a frame are only known at runtime.
+//!
+//! The statically typed [`CompoundIndex`](super::compound_index::CompoundIndex) selects
+//! dimensions with type-level markers such as `Dim1`; a [`DynCompoundIndex`] instead holds a
+//! `Vec` of [`DynIndex`] axes that are addressed by number.
+use super::VariableRange;
+use super::categorical_index::CategoricalRange;
+use super::numeric_range::NumericRangeIndex;
+use super::sparse_numeric_index::SparseNumericIndex;
+
+/// A single axis of a [`DynCompoundIndex`].
+///
+/// Serialized as an internally tagged enum, e.g. `{"kind": "Numeric", "start": 0, "end": 2}`.
+/// Sparse axes are checked to be strictly increasing on deserialization.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DynIndex {
+    /// A contiguous numeric range.
+    Numeric(NumericRangeIndex<i64>),
+    /// A sorted set of numeric keys.
+    Sparse(SparseNumericIndex<i64>),
+    /// A list of string labels.
+    Categorical(CategoricalRange<String>),
+}
+
+/// A value of a [`DynIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynValue<'a> {
+    /// A value of a numeric or sparse axis.
+    Int(i64),
+    /// A label of a categorical axis.
+    Str(&'a str),
+}
+
+impl VariableRange for DynIndex {
+    type Value<'a> = DynValue<'a>;
+
+    /// Returns an iterator over all values of the axis.
+    fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
+        (0..self.size()).map(move |i| self.unflatten_index_value(i))
+    }
+
+    /// Returns the value at the given position on the axis.
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        match self {
+            DynIndex::Numeric(idx) => DynValue::Int(idx.unflatten_index_value(index)),
+            DynIndex::Sparse(idx) => DynValue::Int(idx.unflatten_index_value(index)),
+            DynIndex::Categorical(idx) => DynValue::Str(idx.unflatten_index_value(index)),
+        }
+    }
+
+    /// Returns the number of values on the axis.
+    fn size(&self) -> usize {
+        match self {
+            DynIndex::Numeric(idx) => idx.size(),
+            DynIndex::Sparse(idx) => idx.size(),
+            DynIndex::Categorical(idx) => idx.size(),
+        }
+    }
+
+    /// Returns true unless this is a sparse axis that is not strictly increasing.
+    fn is_well_formed(&self) -> bool {
+        match self {
+            DynIndex::Sparse(idx) => idx.is_well_formed(),
+            DynIndex::Numeric(_) | DynIndex::Categorical(_) => true,
+        }
+    }
+}
+
+/// A compound index whose axes are chosen at runtime, flattened in row-major order
+/// (the last axis varies fastest).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DynCompoundIndex {
+    /// The axes, outermost first.
+    pub indices: Vec<DynIndex>,
+}
+
+/// A value of a [`DynCompoundIndex`]: one position in the compound index, whose
+/// per-axis values are looked up on demand.
+#[derive(Debug, Clone, Copy)]
+pub struct DynCompoundValue<'a> {
+    index: &'a DynCompoundIndex,
+    flat: usize,
+}
+
+impl<'a> DynCompoundValue<'a> {
+    /// The value on the given axis.
+    ///
+    /// Panics if `axis` is out of bounds.
+    pub fn get(&self, axis: usize) -> DynValue<'a> {
+        let indices = &self.index.indices;
+        let inner: usize = indices[axis + 1..].iter().map(|idx| idx.size()).product();
+        let position = (self.flat / inner) % indices[axis].size();
+        indices[axis].unflatten_index_value(position)
+    }
+
+    /// The number of axes.
+    pub fn len(&self) -> usize {
+        self.index.indices.len()
+    }
+
+    /// Returns true if the index has no axes.
+    pub fn is_empty(&self) -> bool {
+        self.index.indices.is_empty()
+    }
+
+    /// The values on all axes, outermost first.
+    pub fn to_vec(&self) -> Vec<DynValue<'a>> {
+        (0..self.len()).map(|axis| self.get(axis)).collect()
+    }
+}
+
+impl DynCompoundIndex {
+    /// Create a new DynCompoundIndex from its axes, outermost first.
+    pub fn new(indices: Vec<DynIndex>) -> Self {
+        Self { indices }
+    }
+
+    /// The number of axes.
+    pub fn n_dims(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+impl VariableRange for DynCompoundIndex {
+    type Value<'a> = DynCompoundValue<'a>;
+
+    /// Returns an iterator over all positions of the compound index in row-major order.
+    fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
+        (0..self.size()).map(move |flat| DynCompoundValue { index: self, flat })
+    }
+
+    /// Returns the position at the given flat index.
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        assert!(index < self.size(), "Index out of bounds.");
+        DynCompoundValue {
+            index: self,
+            flat: index,
+        }
+    }
+
+    /// Returns the product of the axis sizes.
+    fn size(&self) -> usize {
+        self.indices.iter().map(|idx| idx.size()).product()
+    }
+
+    /// Returns true if every axis is well formed.
+    fn is_well_formed(&self) -> bool {
+        self.indices.iter().all(|idx| idx.is_well_formed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_compound_index_values() {
+        let index = DynCompoundIndex::new(vec![
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+            DynIndex::Categorical(CategoricalRange::new(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+            ])),
+        ]);
+
+        assert_eq!(index.size(), 6);
+        let value = index.unflatten_index_value(4);
+        assert_eq!(value.to_vec(), vec![DynValue::Int(1), DynValue::Str("b")]);
+
+        let outer: Vec<DynValue> = index.iter().map(|v| v.get(0)).collect();
+        assert_eq!(
+            outer[..4],
+            [
+                DynValue::Int(0),
+                DynValue::Int(0),
+                DynValue::Int(0),
+                DynValue::Int(1)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        let index = DynCompoundIndex::new(vec![
+            DynIndex::Numeric(NumericRangeIndex::new(0, 2)),
+            DynIndex::Sparse(SparseNumericIndex::new(vec![1, 4, 9].into())),
+        ]);
+
+        let json = serde_json::to_string(&index).unwrap();
+        let round_trip: DynCompoundIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, index);
+
+        let unsorted = r#"{"indices":[{"kind":"Sparse","indices":[4,1]}]}"#;
+        assert!(serde_json::from_str::<DynCompoundIndex>(unsorted).is_err());
+    }
+}
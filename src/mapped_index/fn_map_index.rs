@@ -0,0 +1,75 @@
+use super::VariableRange;
+use std::marker::PhantomData;
+
+/// An index that lazily maps each value of an inner index through a function.
+///
+/// Usually created through [`VariableRange::map_values`].
+#[derive(Debug, Clone)]
+pub struct FnMapIndex<R, F, O> {
+    /// The index whose values are mapped.
+    pub inner: R,
+    /// The function applied to each value of `inner`.
+    pub f: F,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<R, F, O> FnMapIndex<R, F, O>
+where
+    R: VariableRange,
+    F: for<'a> Fn(R::Value<'a>) -> O,
+{
+    /// Create a new FnMapIndex mapping the values of `inner` through `f`.
+    pub fn new(inner: R, f: F) -> Self {
+        Self {
+            inner,
+            f,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<R, F, O> VariableRange for FnMapIndex<R, F, O>
+where
+    R: VariableRange,
+    F: for<'a> Fn(R::Value<'a>) -> O + Sync + Clone,
+    O: Copy,
+{
+    type Value<'a>
+        = O
+    where
+        Self: 'a;
+
+    /// Returns an iterator over the mapped values of the inner index.
+    fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
+        self.inner.iter().map(&self.f)
+    }
+
+    /// Returns the mapped value for the given flat index.
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        (self.f)(self.inner.unflatten_index_value(index))
+    }
+
+    /// Returns the number of values in the inner index.
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+    #[test]
+    fn test_map_values() {
+        let idx = NumericRangeIndex::<i32>::new(0, 3).map_values(|v| v * 2);
+
+        assert_eq!(idx.size(), 3);
+        assert_eq!(idx.iter().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(idx.unflatten_index_value(2), 4);
+
+        // Mapped indices can be mapped again
+        let labels = idx.map_values(|v| if v > 0 { 'p' } else { 'z' });
+        assert_eq!(labels.iter().collect::<String>(), "zpp");
+    }
+}
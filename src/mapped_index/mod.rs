@@ -2,10 +2,13 @@
 
 pub mod categorical_index;
 pub mod compound_index;
+pub mod dyn_index;
+pub mod fn_map_index;
 pub mod numeric_range;
 pub mod one_to_many;
 pub mod singleton_index;
 pub mod sparse_numeric_index;
+pub mod stepped_range;
 pub mod union_range;
 pub mod util;
 
@@ -28,6 +31,94 @@ pub trait VariableRange: Sync + Clone {
 
     /// Returns the total number of values in the index.
     fn size(&self) -> usize;
+
+    /// Returns the first value in the index, or `None` if it is empty.
+    fn first(&self) -> Option<Self::Value<'_>> {
+        (self.size() > 0).then(|| self.unflatten_index_value(0))
+    }
+
+    /// Returns the last value in the index, or `None` if it is empty.
+    fn last(&self) -> Option<Self::Value<'_>> {
+        self.size()
+            .checked_sub(1)
+            .map(|i| self.unflatten_index_value(i))
+    }
+
+    /// Returns the smallest and largest values in the index, or `None` if it is empty.
+    ///
+    /// The default implementation scans every value; ordered indices override it.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::VariableRange;
+    /// assert_eq!(NumericRangeIndex::<i32>::new(0, 5).extent(), Some((0, 4)));
+    /// ```
+    fn extent<'a>(&'a self) -> Option<(Self::Value<'a>, Self::Value<'a>)>
+    where
+        Self::Value<'a>: Ord,
+    {
+        Some((self.iter().min()?, self.iter().max()?))
+    }
+
+    /// Returns true if the index upholds the invariants of its type, e.g. that a sparse
+    /// index is strictly increasing.
+    ///
+    /// Always true for indices built through safe constructors; the default implementation
+    /// has no invariants to check.
+    fn is_well_formed(&self) -> bool {
+        true
+    }
+
+    /// Lazily map each value of the index through `f`, producing a new index.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::VariableRange;
+    /// let idx = NumericRangeIndex::<i32>::new(0, 3).map_values(|v| v * 10);
+    /// assert_eq!(idx.iter().collect::<Vec<_>>(), vec![0, 10, 20]);
+    /// ```
+    fn map_values<O, F>(self, f: F) -> fn_map_index::FnMapIndex<Self, F, O>
+    where
+        Self: Sized,
+        F: for<'a> Fn(Self::Value<'a>) -> O,
+    {
+        fn_map_index::FnMapIndex::new(self, f)
+    }
+}
+
+/// A range that can be consumed to yield its values by ownership rather than by borrow.
+///
+/// [`VariableRange::Value`] may borrow from the range, so it cannot outlive it; this trait
+/// is implemented by ranges whose values can be moved out.
+pub trait IntoValues: VariableRange {
+    /// The owned value type yielded when consuming the range.
+    type OwnedValue;
+
+    /// The iterator returned by [`IntoValues::into_values`].
+    type IntoValues: Iterator<Item = Self::OwnedValue>;
+
+    /// Consume the range, yielding its values in order.
+    fn into_values(self) -> Self::IntoValues;
+}
+
+/// A range that can be fabricated from nothing but its size, e.g. `0..n`.
+///
+/// Lets generic operations create a fresh index of the appropriate type for reshaped data.
+pub trait FromSize: VariableRange {
+    /// Create a range holding exactly `n` values.
+    fn from_size(n: usize) -> Self;
+}
+
+/// A range from which a contiguous run of its values can be cut out as a range of the same type.
+pub trait SubRange: VariableRange {
+    /// The range holding the values at flat indices `start..end`.
+    ///
+    /// # Panics
+    ///
+    /// Implementations must panic if `start > end` or `end > self.size()`.
+    fn sub_range(&self, start: usize, end: usize) -> Self;
 }
 
 impl<'b, T: VariableRange + ?Sized> VariableRange for &'b T {
@@ -47,4 +138,8 @@ impl<'b, T: VariableRange + ?Sized> VariableRange for &'b T {
     fn size(&self) -> usize {
         (*self).size()
     }
+
+    fn is_well_formed(&self) -> bool {
+        (*self).is_well_formed()
+    }
 }
@@ -1,4 +1,4 @@
-use super::VariableRange;
+use super::{FromSize, IntoValues, SubRange, VariableRange};
 use std::fmt::Debug;
 
 pub trait NumericRangeValue: 'static + Copy + Ord + Debug + Sync + Send {
@@ -6,6 +6,19 @@ pub trait NumericRangeValue: 'static + Copy + Ord + Debug + Sync + Send {
 
     fn nth_next(&self, n: usize) -> Self;
 
+    /// The value before this one. Only needed for descending ranges.
+    ///
+    /// # Panics
+    /// The default implementation panics; override it to support descending ranges.
+    fn prev(&self) -> Self {
+        panic!("{self:?}: this type does not support descending ranges")
+    }
+
+    /// The value `n` steps before this one. Defaults to calling [`prev`](Self::prev) `n` times.
+    fn nth_prev(&self, n: usize) -> Self {
+        (0..n).fold(*self, |value, _| value.prev())
+    }
+
     fn distance(&self, other: &Self) -> usize;
 }
 
@@ -20,6 +33,14 @@ macro_rules! impl_numeric_range_value {
                 self + n as Self
             }
 
+            fn prev(&self) -> Self {
+                self - 1
+            }
+
+            fn nth_prev(&self, n: usize) -> Self {
+                self - n as Self
+            }
+
             fn distance(&self, other: &Self) -> usize {
                 if *self > *other {
                     (self - other) as usize
@@ -58,6 +79,14 @@ macro_rules! nrange_newtype {
                 Self(self.0.nth_next(n))
             }
 
+            fn prev(&self) -> Self {
+                Self(self.0.prev())
+            }
+
+            fn nth_prev(&self, n: usize) -> Self {
+                Self(self.0.nth_prev(n))
+            }
+
             fn distance(&self, other: &Self) -> usize {
                 self.0.distance(&other.0)
             }
@@ -83,6 +112,14 @@ macro_rules! nrange_newtype {
                 Self(self.0.nth_next(n))
             }
 
+            fn prev(&self) -> Self {
+                Self(self.0.prev())
+            }
+
+            fn nth_prev(&self, n: usize) -> Self {
+                Self(self.0.nth_prev(n))
+            }
+
             fn distance(&self, other: &Self) -> usize {
                 self.0.distance(&other.0)
             }
@@ -91,6 +128,8 @@ macro_rules! nrange_newtype {
 }
 
 /// An index representing a numeric range from `start` to `end` (exclusive).
+///
+/// If `start > end` the range is descending, counting down from `start` to just above `end`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub struct NumericRangeIndex<I: Debug> {
@@ -100,6 +139,23 @@ pub struct NumericRangeIndex<I: Debug> {
     pub end: I,
 }
 
+/// Reasons why a [`NumericRangeIndex`] cannot be constructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// `start` is not less than `end`.
+    EmptyOrInverted,
+}
+
+impl std::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeError::EmptyOrInverted => write!(f, "start must be less than end"),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
 impl<I: Debug + Ord> NumericRangeIndex<I> {
     /// Create a new numeric range index [start, end) (end exclusive).
     ///
@@ -114,6 +170,55 @@ impl<I: Debug + Ord> NumericRangeIndex<I> {
         assert!(start < end, "Start must be less than end.");
         Self { start, end }
     }
+
+    /// Like [`NumericRangeIndex::new`], but returns an error instead of panicking if
+    /// `start` is not less than `end`.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::numeric_range::RangeError;
+    /// assert!(NumericRangeIndex::<i32>::checked_new(0, 3).is_ok());
+    /// assert_eq!(NumericRangeIndex::<i32>::checked_new(3, 3), Err(RangeError::EmptyOrInverted));
+    /// ```
+    pub fn checked_new(start: I, end: I) -> Result<Self, RangeError> {
+        if start < end {
+            Ok(Self { start, end })
+        } else {
+            Err(RangeError::EmptyOrInverted)
+        }
+    }
+
+    /// Create a new descending numeric range index counting down from `start` to just
+    /// above `end` (end exclusive).
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::VariableRange;
+    /// let idx = NumericRangeIndex::<i32>::descending(3, 0);
+    /// assert_eq!(idx.iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn descending(start: I, end: I) -> Self {
+        assert!(start > end, "Start must be greater than end.");
+        Self { start, end }
+    }
+
+    /// Returns true if the range counts down from `start` to `end`.
+    pub fn is_descending(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+impl<I: NumericRangeValue> NumericRangeIndex<I> {
+    /// The value `n` steps from `start`, in the direction of the range.
+    fn nth_from_start(&self, n: usize) -> I {
+        if self.is_descending() {
+            self.start.nth_prev(n)
+        } else {
+            self.start.nth_next(n)
+        }
+    }
 }
 
 impl<I: Clone + Debug> Clone for NumericRangeIndex<I> {
@@ -133,28 +238,189 @@ where
 
     /// Returns an iterator over all numeric values in the range.
     fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
-        let mut next = self.start;
-        let end = self.end;
-
-        std::iter::from_fn(move || {
-            if next >= end {
-                None
-            } else {
-                let current = next;
-                next = next.next();
-                Some(current)
-            }
-        })
+        self.clone().into_values()
     }
 
     /// Returns the numeric value for a given flat index.
     fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
         assert!(index < self.size(), "Index out of bounds.");
-        self.start.nth_next(index)
+        self.nth_from_start(index)
     }
 
     /// Returns the number of values in the numeric range index.
     fn size(&self) -> usize {
         self.start.distance(&self.end)
     }
+
+    /// Returns the smallest and largest values from the ends of the range.
+    fn extent<'a>(&'a self) -> Option<(Self::Value<'a>, Self::Value<'a>)>
+    where
+        Self::Value<'a>: Ord,
+    {
+        let (first, last) = (self.first()?, self.last()?);
+        Some((first.min(last), first.max(last)))
+    }
+}
+
+impl<I: NumericRangeValue> SubRange for NumericRangeIndex<I> {
+    fn sub_range(&self, start: usize, end: usize) -> Self {
+        assert!(
+            start <= end && end <= self.size(),
+            "Sub-range out of bounds."
+        );
+        Self {
+            start: self.nth_from_start(start),
+            end: self.nth_from_start(end),
+        }
+    }
+}
+
+impl FromSize for NumericRangeIndex<usize> {
+    /// Create the range `0..n`.
+    fn from_size(n: usize) -> Self {
+        Self { start: 0, end: n }
+    }
+}
+
+/// Owning iterator over the values of a [`NumericRangeIndex`].
+#[derive(Debug, Clone)]
+pub struct NumericRangeValues<I> {
+    next: I,
+    end: I,
+    descending: bool,
+}
+
+impl<I: NumericRangeValue> Iterator for NumericRangeValues<I> {
+    type Item = I;
+
+    fn next(&mut self) -> Option<I> {
+        if self.next == self.end {
+            return None;
+        }
+        let current = self.next;
+        self.next = if self.descending {
+            current.prev()
+        } else {
+            current.next()
+        };
+        Some(current)
+    }
+}
+
+impl<I: NumericRangeValue> IntoValues for NumericRangeIndex<I> {
+    type OwnedValue = I;
+    type IntoValues = NumericRangeValues<I>;
+
+    fn into_values(self) -> Self::IntoValues {
+        let descending = self.is_descending();
+        NumericRangeValues {
+            next: self.start,
+            end: self.end,
+            descending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extent() {
+        assert_eq!(NumericRangeIndex::<i32>::new(0, 5).extent(), Some((0, 4)));
+        assert_eq!(
+            NumericRangeIndex::<i32>::descending(5, 0).extent(),
+            Some((1, 5))
+        );
+        assert_eq!(NumericRangeIndex::<i32> { start: 2, end: 2 }.extent(), None);
+    }
+
+    #[test]
+    fn test_checked_new() {
+        assert_eq!(
+            NumericRangeIndex::<i32>::checked_new(5, 3),
+            Err(RangeError::EmptyOrInverted)
+        );
+        assert_eq!(
+            NumericRangeIndex::<i32>::checked_new(0, 3),
+            Ok(NumericRangeIndex::new(0, 3))
+        );
+    }
+
+    #[test]
+    fn test_from_size() {
+        let idx = NumericRangeIndex::<usize>::from_size(3);
+        assert_eq!(idx, NumericRangeIndex::new(0, 3));
+        assert_eq!(idx.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        assert_eq!(NumericRangeIndex::<usize>::from_size(0).size(), 0);
+    }
+
+    #[test]
+    fn test_sub_range() {
+        let idx = NumericRangeIndex::<i32>::new(10, 15);
+        assert_eq!(idx.sub_range(1, 3), NumericRangeIndex::new(11, 13));
+        assert_eq!(idx.sub_range(5, 5).size(), 0);
+    }
+
+    #[test]
+    fn test_descending() {
+        let idx = NumericRangeIndex::<i32>::descending(5, 0);
+
+        assert!(idx.is_descending());
+        assert_eq!(idx.size(), 5);
+        assert_eq!(idx.iter().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(idx.unflatten_index_value(0), 5);
+        assert_eq!(idx.unflatten_index_value(4), 1);
+        assert_eq!(
+            idx.clone().into_values().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+        assert_eq!(idx.sub_range(1, 3), NumericRangeIndex::descending(4, 2));
+
+        // Unsigned ranges can count down to zero
+        let idx = NumericRangeIndex::<usize>::descending(2, 0);
+        assert_eq!(idx.iter().collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    /// A value type that only implements the required, forward-stepping methods.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Forward(u8);
+
+    impl NumericRangeValue for Forward {
+        fn next(&self) -> Self {
+            Forward(self.0 + 1)
+        }
+
+        fn nth_next(&self, n: usize) -> Self {
+            Forward(self.0 + n as u8)
+        }
+
+        fn distance(&self, other: &Self) -> usize {
+            self.0.abs_diff(other.0) as usize
+        }
+    }
+
+    #[test]
+    fn test_forward_only_value() {
+        let idx = NumericRangeIndex::new(Forward(1), Forward(4));
+        assert_eq!(
+            idx.iter().collect::<Vec<_>>(),
+            vec![Forward(1), Forward(2), Forward(3)]
+        );
+        assert_eq!(idx.unflatten_index_value(2), Forward(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not support descending ranges")]
+    fn test_forward_only_value_descending() {
+        let idx = NumericRangeIndex::descending(Forward(4), Forward(1));
+        let _ = idx.unflatten_index_value(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Start must be greater than end.")]
+    fn test_descending_rejects_ascending_bounds() {
+        let _ = NumericRangeIndex::<i32>::descending(0, 5);
+    }
 }
@@ -1,4 +1,7 @@
 use super::VariableRange;
+use super::util::offsets::{cumulative_ends, locate};
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 
 /// A one-to-many range that associates each element of a left-hand range
 /// with a corresponding range from a vector, then flattens the associated
@@ -8,12 +11,45 @@ use super::VariableRange;
 /// of its values to the resulting sequence. The total size is the sum of the
 /// sizes of all right-hand ranges.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct OneToManyRange<L: VariableRange, R: VariableRange> {
     /// The left-hand range used to define association and order.
     pub left: L,
     /// The right-hand ranges, one per element of `left`.
-    pub rights: Vec<R>,
+    rights: Vec<R>,
+    /// Cumulative end offset of each right-hand range, built on first lookup.
+    ///
+    /// Cleared whenever `rights` is handed out mutably, so it never goes stale.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    offsets: OnceLock<Vec<usize>>,
+}
+
+impl<L, R> PartialEq for OneToManyRange<L, R>
+where
+    L: VariableRange + PartialEq,
+    R: VariableRange + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.left == other.left && self.rights == other.rights
+    }
+}
+
+impl<L, R> Eq for OneToManyRange<L, R>
+where
+    L: VariableRange + Eq,
+    R: VariableRange + Eq,
+{
+}
+
+impl<L, R> Hash for OneToManyRange<L, R>
+where
+    L: VariableRange + Hash,
+    R: VariableRange + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.left.hash(state);
+        self.rights.hash(state);
+    }
 }
 
 impl<L: VariableRange, R: VariableRange> OneToManyRange<L, R> {
@@ -26,7 +62,34 @@ impl<L: VariableRange, R: VariableRange> OneToManyRange<L, R> {
             left.size(),
             "rights length must match left size"
         );
-        Self { left, rights }
+        Self {
+            left,
+            rights,
+            offsets: OnceLock::new(),
+        }
+    }
+
+    /// The right-hand ranges, one per element of `left`.
+    pub fn rights(&self) -> &[R] {
+        &self.rights
+    }
+
+    /// Mutable access to the right-hand ranges.
+    ///
+    /// Their sizes may be changed freely; the cached offsets are rebuilt on the next lookup.
+    pub fn rights_mut(&mut self) -> &mut [R] {
+        self.offsets.take();
+        &mut self.rights
+    }
+
+    /// Consume the range, returning the left-hand range and the right-hand ranges.
+    pub fn into_parts(self) -> (L, Vec<R>) {
+        (self.left, self.rights)
+    }
+
+    /// The cumulative end offsets of `rights`, computed on first use.
+    fn cached_offsets(&self) -> &[usize] {
+        self.offsets.get_or_init(|| cumulative_ends(&self.rights))
     }
 
     /// Number of associations (i.e., size of the left-hand range).
@@ -58,21 +121,18 @@ impl<L: VariableRange, R: VariableRange> VariableRange for OneToManyRange<L, R>
         (0..total).map(move |i| self.unflatten_index_value(i))
     }
 
-    /// Return the value corresponding to a flat index by scanning through the right-hand ranges.
-    fn unflatten_index_value(&self, mut index: usize) -> Self::Value<'_> {
-        for r in &self.rights {
-            let sz = r.size();
-            if index < sz {
-                return r.unflatten_index_value(index);
-            }
-            index -= sz;
-        }
-        panic!("Index out of bounds: {} (size: {})", index, self.size());
+    /// Return the value corresponding to a flat index, binary-searching the cached offsets
+    /// for the owning right-hand range.
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        let Some((i, local)) = locate(self.cached_offsets(), index) else {
+            panic!("Index out of bounds: {} (size: {})", index, self.size());
+        };
+        self.rights[i].unflatten_index_value(local)
     }
 
     /// Sum of sizes of all right-hand ranges.
     fn size(&self) -> usize {
-        self.rights.iter().map(|r| r.size()).sum()
+        self.cached_offsets().last().copied().unwrap_or(0)
     }
 }
 
@@ -155,4 +215,46 @@ mod tests {
         // Provide only one right range; should panic
         let _ = OneToManyRange::new(left, vec![r0]);
     }
+
+    #[test]
+    fn test_one_to_many_many_ranges() {
+        // 1000 right-hand ranges of varying size (including empty ones)
+        let n = 1000usize;
+        let left = NumericRangeIndex::new(0usize, n);
+        let rights: Vec<_> = (0..n)
+            .map(|i| NumericRangeIndex {
+                start: i * 100,
+                end: i * 100 + i % 4,
+            })
+            .collect();
+        let expected: Vec<usize> = rights.iter().flat_map(|r| r.iter()).collect();
+        let otm = OneToManyRange::new(left.clone(), rights.clone());
+
+        assert_eq!(otm.size(), expected.len());
+        for (flat, value) in expected.iter().enumerate() {
+            assert_eq!(otm.unflatten_index_value(flat), *value);
+        }
+
+        // A fresh range builds its cache on first use and agrees
+        let uncached = OneToManyRange::new(left, rights);
+        assert_eq!(uncached, otm);
+        assert!(uncached.iter().eq(otm.iter()));
+        assert_eq!(uncached.size(), otm.size());
+    }
+
+    #[test]
+    fn test_one_to_many_mutate_rights() {
+        let left = NumericRangeIndex::new(0usize, 2usize);
+        let r0 = NumericRangeIndex::new(10usize, 12usize); // 10,11
+        let r1 = NumericRangeIndex::new(20usize, 23usize); // 20,21,22
+        let mut otm = OneToManyRange::new(left, vec![r0, r1]);
+        assert_eq!(otm.size(), 5);
+
+        // Shrink the first range in place; the cached offsets must follow
+        otm.rights_mut()[0] = NumericRangeIndex::new(10usize, 11usize);
+        assert_eq!(otm.size(), 4);
+        assert_eq!(otm.unflatten_index_value(1), 20);
+        assert_eq!(otm.iter().collect::<Vec<_>>(), vec![10, 20, 21, 22]);
+        assert_eq!(otm.rights().len(), 2);
+    }
 }
@@ -1,10 +1,18 @@
-use super::VariableRange;
+use super::{FromSize, IntoValues, SubRange, VariableRange};
 use sorted_vec::SortedSet;
+use std::ops::Sub;
 
 /// A sparse numeric index, holding a sorted Vec of i32 indices.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SparseNumericIndex<I: Ord> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            deserialize_with = "deserialize_strictly_increasing",
+            bound(deserialize = "I: serde::Deserialize<'de>")
+        )
+    )]
     pub indices: SortedSet<I>,
 }
 
@@ -22,6 +30,77 @@ impl<I: Ord + Copy> SparseNumericIndex<I> {
     pub fn new(indices: SortedSet<I>) -> Self {
         Self { indices }
     }
+
+    /// Create a new SparseNumericIndex from a Vec that is assumed to be strictly increasing,
+    /// without checking or sorting it.
+    ///
+    /// # Safety
+    ///
+    /// `indices` must be strictly increasing. Lookups rely on binary search and will
+    /// silently return wrong results otherwise; see [`Self::is_strictly_increasing`].
+    pub unsafe fn from_sorted_unchecked(indices: Vec<I>) -> Self {
+        Self {
+            // SAFETY: forwarded to the caller.
+            indices: unsafe { SortedSet::from_sorted(indices) },
+        }
+    }
+
+    /// Returns true if every index is strictly greater than the one before it.
+    ///
+    /// This always holds for indices built through safe constructors.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::SparseNumericIndex;
+    /// use sorted_vec::SortedSet;
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![1_i64, 3, 5]));
+    /// assert!(idx.is_strictly_increasing());
+    /// ```
+    pub fn is_strictly_increasing(&self) -> bool {
+        is_strictly_increasing(&self.indices)
+    }
+}
+
+impl<I: Copy + Ord + Sub<Output = I>> SparseNumericIndex<I> {
+    /// Returns the pairs of consecutive keys that are more than `expected_step` apart,
+    /// i.e. where keys are missing.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::SparseNumericIndex;
+    /// use sorted_vec::SortedSet;
+    /// let idx = SparseNumericIndex::new(SortedSet::from(vec![0_i64, 10, 20, 50]));
+    /// assert_eq!(idx.gaps(10), vec![(20, 50)]);
+    /// ```
+    pub fn gaps(&self, expected_step: I) -> Vec<(I, I)> {
+        self.indices
+            .windows(2)
+            .filter(|w| w[1] - w[0] > expected_step)
+            .map(|w| (w[0], w[1]))
+            .collect()
+    }
+}
+
+fn is_strictly_increasing<I: Ord>(indices: &[I]) -> bool {
+    indices.windows(2).all(|w| w[0] < w[1])
+}
+
+/// Deserialize the indices, rejecting any sequence that is not strictly increasing.
+#[cfg(feature = "serde")]
+fn deserialize_strictly_increasing<'de, De, I>(deserializer: De) -> Result<SortedSet<I>, De::Error>
+where
+    De: serde::Deserializer<'de>,
+    I: Ord + serde::Deserialize<'de>,
+{
+    use serde::Deserialize;
+    use serde::de::Error;
+    let indices = Vec::<I>::deserialize(deserializer)?;
+    if !is_strictly_increasing(&indices) {
+        return Err(De::Error::custom(
+            "sparse index keys must be strictly increasing",
+        ));
+    }
+    Ok(SortedSet::from_unsorted(indices))
 }
 
 impl<I: Copy + 'static + Ord + Sync> VariableRange for SparseNumericIndex<I> {
@@ -38,4 +117,114 @@ impl<I: Copy + 'static + Ord + Sync> VariableRange for SparseNumericIndex<I> {
     fn size(&self) -> usize {
         self.indices.len()
     }
+
+    fn extent<'a>(&'a self) -> Option<(I, I)>
+    where
+        Self::Value<'a>: Ord,
+    {
+        Some((self.first()?, self.last()?))
+    }
+
+    fn is_well_formed(&self) -> bool {
+        self.is_strictly_increasing()
+    }
+}
+
+impl<I: Copy + 'static + Ord + Sync> IntoValues for SparseNumericIndex<I> {
+    type OwnedValue = I;
+    type IntoValues = std::vec::IntoIter<I>;
+
+    fn into_values(self) -> Self::IntoValues {
+        self.indices.into_vec().into_iter()
+    }
+}
+
+impl<I: Copy + 'static + Ord + Sync> SubRange for SparseNumericIndex<I> {
+    fn sub_range(&self, start: usize, end: usize) -> Self {
+        // SAFETY: a contiguous run of a strictly increasing sequence is strictly increasing.
+        unsafe { Self::from_sorted_unchecked(self.indices[start..end].to_vec()) }
+    }
+}
+
+impl FromSize for SparseNumericIndex<i64> {
+    /// Create the index `{0, 1, ..., n - 1}`.
+    fn from_size(n: usize) -> Self {
+        // SAFETY: 0..n is strictly increasing.
+        unsafe { Self::from_sorted_unchecked((0..n as i64).collect()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_strictly_increasing() {
+        let idx = SparseNumericIndex::<i32>::new(vec![5, 1, 3].into());
+        assert!(idx.is_strictly_increasing());
+
+        let corrupt = unsafe { SparseNumericIndex::<i32>::from_sorted_unchecked(vec![5, 1, 3]) };
+        assert!(!corrupt.is_strictly_increasing());
+
+        let duplicated = unsafe { SparseNumericIndex::<i32>::from_sorted_unchecked(vec![1, 1]) };
+        assert!(!duplicated.is_strictly_increasing());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rejects_out_of_order() {
+        let idx = SparseNumericIndex::<i64>::new(vec![1, 3, 5].into());
+        let json = serde_json::to_string(&idx).unwrap();
+        let round_trip: SparseNumericIndex<i64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, idx);
+
+        let out_of_order = r#"{"indices":[5,1,3]}"#;
+        let err = serde_json::from_str::<SparseNumericIndex<i64>>(out_of_order).unwrap_err();
+        assert!(err.to_string().contains("strictly increasing"));
+
+        let duplicated = r#"{"indices":[1,1,3]}"#;
+        assert!(serde_json::from_str::<SparseNumericIndex<i64>>(duplicated).is_err());
+    }
+
+    #[test]
+    fn test_extent() {
+        let idx = SparseNumericIndex::new(SortedSet::from(vec![3, 7, 9]));
+        assert_eq!(idx.extent(), Some((3, 9)));
+
+        let empty = SparseNumericIndex::<i32>::new(SortedSet::new());
+        assert_eq!(empty.extent(), None);
+    }
+
+    #[test]
+    fn test_first_last() {
+        let idx = SparseNumericIndex::<i64>::new(vec![7, 3, 5].into());
+        assert_eq!(idx.first(), Some(3));
+        assert_eq!(idx.last(), Some(7));
+
+        let empty = SparseNumericIndex::<i64>::new(SortedSet::new());
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+
+        let range = crate::NumericRangeIndex::<i32>::new(-2, 4);
+        assert_eq!(range.first(), Some(-2));
+        assert_eq!(range.last(), Some(3));
+    }
+
+    #[test]
+    fn test_from_size() {
+        let idx = SparseNumericIndex::<i64>::from_size(3);
+        assert_eq!(idx.indices.to_vec(), vec![0, 1, 2]);
+        assert!(idx.is_strictly_increasing());
+        assert_eq!(SparseNumericIndex::<i64>::from_size(0).size(), 0);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let idx = SparseNumericIndex::<i64>::new(vec![0, 1, 5, 6].into());
+        assert_eq!(idx.gaps(1), vec![(1, 5)]);
+        assert_eq!(idx.gaps(4), vec![]);
+
+        let idx = SparseNumericIndex::<u32>::new(vec![2, 4, 8, 9, 20].into());
+        assert_eq!(idx.gaps(2), vec![(4, 8), (9, 20)]);
+    }
 }
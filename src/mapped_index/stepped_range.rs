@@ -0,0 +1,53 @@
+use super::VariableRange;
+use super::numeric_range::NumericRangeValue;
+
+/// An index of `len` numeric values starting at `start` and spaced `step` apart.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SteppedRangeIndex<I> {
+    /// The first value.
+    pub start: I,
+    /// The distance between consecutive values.
+    pub step: usize,
+    /// The number of values.
+    pub len: usize,
+}
+
+impl<I> SteppedRangeIndex<I> {
+    /// Create a new SteppedRangeIndex `start, start + step, ...` holding `len` values.
+    ///
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::mapped_index::VariableRange;
+    /// use slice_and_dice::mapped_index::stepped_range::SteppedRangeIndex;
+    /// let idx = SteppedRangeIndex::new(10_i32, 5, 3);
+    /// assert_eq!(idx.iter().collect::<Vec<_>>(), vec![10, 15, 20]);
+    /// ```
+    pub fn new(start: I, step: usize, len: usize) -> Self {
+        assert!(step > 0, "Step must be positive.");
+        Self { start, step, len }
+    }
+}
+
+impl<I: NumericRangeValue> VariableRange for SteppedRangeIndex<I> {
+    type Value<'a> = I;
+
+    /// Returns an iterator over all values in the stepped range.
+    fn iter(&self) -> impl Iterator<Item = Self::Value<'_>> + Clone {
+        let (start, step) = (self.start, self.step);
+        (0..self.len).map(move |i| start.nth_next(i * step))
+    }
+
+    /// Returns the value at the given flat index.
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        assert!(index < self.len, "Index out of bounds.");
+        self.start.nth_next(index * self.step)
+    }
+
+    /// Returns the number of values in the stepped range.
+    fn size(&self) -> usize {
+        self.len
+    }
+}
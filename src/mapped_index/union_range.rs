@@ -1,4 +1,7 @@
 use super::VariableRange;
+use super::util::offsets::{cumulative_ends, locate};
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 
 /// A range that is the concatenation (union) of multiple other ranges
 /// with the same value type.
@@ -7,23 +10,72 @@ use super::VariableRange;
 /// second, and so on. Sizes are summed. Indexing is delegated to the
 /// appropriate inner range based on cumulative sizes.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct UnionRange<R: VariableRange> {
     /// The inner ranges that form the union.
-    pub ranges: Vec<R>,
+    ranges: Vec<R>,
+    /// Cumulative end offset of each inner range, built on first lookup and extended by `push`.
+    ///
+    /// Cleared whenever `ranges` is handed out mutably, so it never goes stale.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    offsets: OnceLock<Vec<usize>>,
+}
+
+impl<R: VariableRange + PartialEq> PartialEq for UnionRange<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges == other.ranges
+    }
+}
+
+impl<R: VariableRange + Eq> Eq for UnionRange<R> {}
+
+impl<R: VariableRange + Hash> Hash for UnionRange<R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ranges.hash(state);
+    }
 }
 
 impl<R: VariableRange> UnionRange<R> {
     /// Create a new UnionRange from a vector of ranges.
     pub const fn new(ranges: Vec<R>) -> Self {
-        Self { ranges }
+        Self {
+            ranges,
+            offsets: OnceLock::new(),
+        }
     }
 
     /// Push a new range to the end of the union.
     pub fn push(&mut self, range: R) {
+        if let Some(offsets) = self.offsets.get_mut() {
+            let end = offsets.last().copied().unwrap_or(0) + range.size();
+            offsets.push(end);
+        }
         self.ranges.push(range);
     }
 
+    /// The inner ranges that form the union.
+    pub fn ranges(&self) -> &[R] {
+        &self.ranges
+    }
+
+    /// Mutable access to the inner ranges.
+    ///
+    /// Their sizes may be changed freely; the cached offsets are rebuilt on the next lookup.
+    pub fn ranges_mut(&mut self) -> &mut [R] {
+        self.offsets.take();
+        &mut self.ranges
+    }
+
+    /// Consume the union, returning its inner ranges.
+    pub fn into_ranges(self) -> Vec<R> {
+        self.ranges
+    }
+
+    /// The cumulative end offsets of `ranges`, computed on first use.
+    fn cached_offsets(&self) -> &[usize] {
+        self.offsets.get_or_init(|| cumulative_ends(&self.ranges))
+    }
+
     /// Number of inner ranges.
     pub fn len(&self) -> usize {
         self.ranges.len()
@@ -33,6 +85,28 @@ impl<R: VariableRange> UnionRange<R> {
     pub fn is_empty(&self) -> bool {
         self.ranges.is_empty()
     }
+
+    /// Returns true if both unions yield the same sequence of values, regardless of how
+    /// that sequence is split across inner ranges.
+    ///
+    /// Unlike `==`, which compares the inner ranges one by one, this treats
+    /// `[0..3, 3..5]` and `[0..2, 2..5]` as equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use slice_and_dice::NumericRangeIndex;
+    /// use slice_and_dice::mapped_index::union_range::UnionRange;
+    /// let a = UnionRange::new(vec![NumericRangeIndex::new(0, 3), NumericRangeIndex::new(3, 5)]);
+    /// let b = UnionRange::new(vec![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(2, 5)]);
+    /// assert_ne!(a, b);
+    /// assert!(a.values_eq(&b));
+    /// ```
+    pub fn values_eq(&self, other: &Self) -> bool
+    where
+        for<'a> R::Value<'a>: PartialEq,
+    {
+        self.size() == other.size() && self.iter().eq(other.iter())
+    }
 }
 
 impl<R: VariableRange> VariableRange for UnionRange<R> {
@@ -52,20 +126,16 @@ impl<R: VariableRange> VariableRange for UnionRange<R> {
 
     /// Find the appropriate inner range based on cumulative sizes and
     /// return the corresponding value.
-    fn unflatten_index_value(&self, mut index: usize) -> Self::Value<'_> {
-        for r in &self.ranges {
-            let sz = r.size();
-            if index < sz {
-                return r.unflatten_index_value(index);
-            }
-            index -= sz;
-        }
-        panic!("Index out of bounds: {} (size: {})", index, self.size());
+    fn unflatten_index_value(&self, index: usize) -> Self::Value<'_> {
+        let Some((i, local)) = locate(self.cached_offsets(), index) else {
+            panic!("Index out of bounds: {} (size: {})", index, self.size());
+        };
+        self.ranges[i].unflatten_index_value(local)
     }
 
     /// Sum of sizes of all inner ranges.
     fn size(&self) -> usize {
-        self.ranges.iter().map(|r| r.size()).sum()
+        self.cached_offsets().last().copied().unwrap_or(0)
     }
 }
 
@@ -134,4 +204,76 @@ mod tests {
         let vals: Vec<u32> = union.iter().collect();
         assert_eq!(vals, vec![42, 7]);
     }
+
+    #[test]
+    fn test_union_values_eq() {
+        let a = UnionRange::new(vec![
+            NumericRangeIndex::new(0usize, 3usize),
+            NumericRangeIndex::new(3usize, 5usize),
+        ]);
+        let mut b = UnionRange::new(Vec::new());
+        b.push(NumericRangeIndex::new(0usize, 1usize));
+        b.push(NumericRangeIndex::new(1usize, 4usize));
+        b.push(NumericRangeIndex::new(4usize, 5usize));
+
+        assert_ne!(a, b);
+        assert!(a.values_eq(&b));
+        assert!(b.values_eq(&a));
+
+        // Same segments, different values
+        let c = UnionRange::new(vec![
+            NumericRangeIndex::new(0usize, 3usize),
+            NumericRangeIndex::new(4usize, 6usize),
+        ]);
+        assert!(!a.values_eq(&c));
+
+        // A prefix of the values is not equal
+        let d = UnionRange::new(vec![NumericRangeIndex::new(0usize, 4usize)]);
+        assert!(!a.values_eq(&d));
+    }
+
+    #[test]
+    fn test_union_many_segments() {
+        let mut union = UnionRange::new(Vec::new());
+        for i in 0..500usize {
+            union.push(CopySingletonRange::new(i * 3));
+        }
+
+        assert_eq!(union.size(), 500);
+        for flat in [0, 1, 2, 137, 250, 498, 499] {
+            assert_eq!(union.unflatten_index_value(flat), flat * 3);
+        }
+
+        // A fresh union builds its cache on first use and agrees
+        let uncached = UnionRange::new(union.ranges().to_vec());
+        assert_eq!(uncached, union);
+        assert!(uncached.iter().eq(union.iter()));
+
+        // Pushing onto a cached union extends the cache
+        union.push(CopySingletonRange::new(1500));
+        assert_eq!(union.cached_offsets().len(), 501);
+        assert_eq!(union.unflatten_index_value(500), 1500);
+    }
+
+    #[test]
+    fn test_union_mutate_ranges() {
+        let mut union = UnionRange::new(vec![
+            NumericRangeIndex::new(0usize, 3usize),
+            NumericRangeIndex::new(10usize, 12usize),
+        ]);
+        assert_eq!(union.size(), 5);
+
+        // Grow the first range in place; the cached offsets must follow
+        union.ranges_mut()[0] = NumericRangeIndex::new(0usize, 5usize);
+        assert_eq!(union.size(), 7);
+        assert_eq!(union.unflatten_index_value(4), 4);
+        assert_eq!(union.unflatten_index_value(5), 10);
+    }
+
+    #[test]
+    fn test_union_new_in_static() {
+        static EMPTY: UnionRange<NumericRangeIndex<usize>> = UnionRange::new(Vec::new());
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.size(), 0);
+    }
 }
@@ -1,3 +1,4 @@
 pub mod as_refs;
 pub mod concat;
+pub mod offsets;
 pub mod pluck_split;
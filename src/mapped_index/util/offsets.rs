@@ -0,0 +1,62 @@
+//! Prefix-sum helpers for ranges that are the concatenation of several inner ranges.
+//!
+//! The cumulative end offsets of the inner ranges let a flat index be mapped to its
+//! owning range with a binary search instead of a linear scan.
+//!
+//! # Example
+//! ```
+//! use slice_and_dice::NumericRangeIndex;
+//! use slice_and_dice::mapped_index::util::offsets::{cumulative_ends, locate};
+//!
+//! let ranges = vec![NumericRangeIndex::new(0, 2), NumericRangeIndex::new(10, 13)];
+//! let ends = cumulative_ends(&ranges);
+//! assert_eq!(ends, vec![2, 5]);
+//! assert_eq!(locate(&ends, 3), Some((1, 1)));
+//! assert_eq!(locate(&ends, 5), None);
+//! ```
+use crate::mapped_index::VariableRange;
+
+/// The cumulative end offset of each range, i.e. the running sum of their sizes.
+pub fn cumulative_ends<R: VariableRange>(ranges: &[R]) -> Vec<usize> {
+    ranges
+        .iter()
+        .scan(0, |end, r| {
+            *end += r.size();
+            Some(*end)
+        })
+        .collect()
+}
+
+/// Find the range containing flat `index`, returning its position and the index local to it.
+///
+/// Returns `None` if `index` is past the last end offset.
+pub fn locate(ends: &[usize], index: usize) -> Option<(usize, usize)> {
+    let i = ends.partition_point(|&end| end <= index);
+    if i == ends.len() {
+        return None;
+    }
+    let start = if i == 0 { 0 } else { ends[i - 1] };
+    Some((i, index - start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapped_index::numeric_range::NumericRangeIndex;
+
+    #[test]
+    fn test_locate_skips_empty_ranges() {
+        let ranges = vec![
+            NumericRangeIndex::new(0usize, 2usize),
+            NumericRangeIndex { start: 5, end: 5 },
+            NumericRangeIndex::new(7usize, 8usize),
+        ];
+        let ends = cumulative_ends(&ranges);
+        assert_eq!(ends, vec![2, 2, 3]);
+        assert_eq!(locate(&ends, 0), Some((0, 0)));
+        assert_eq!(locate(&ends, 1), Some((0, 1)));
+        assert_eq!(locate(&ends, 2), Some((2, 0)));
+        assert_eq!(locate(&ends, 3), None);
+        assert_eq!(locate(&[], 0), None);
+    }
+}
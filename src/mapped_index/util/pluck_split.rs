@@ -31,6 +31,8 @@ pub type PluckAt<At, List> = <List as PluckSplitImpl<At>>::Extract;
 pub type PluckLeft<At, List> = <List as PluckSplitImpl<At>>::Left;
 pub type PluckRight<At, List> = <List as PluckSplitImpl<At>>::Right;
 pub type PluckRemainder<At, List> = HLConcat<PluckLeft<At, List>, PluckRight<At, List>>;
+pub type PluckReplace<At, List, With> =
+    HLConcat<PluckLeft<At, List>, HCons<With, PluckRight<At, List>>>;
 
 impl<T> PluckSplit for T {
     fn pluck_split<At>(